@@ -0,0 +1,64 @@
+//! Compares placing images with slice copies against the per pixel `GenericImage::copy_from`.
+//!
+//! Run with `cargo bench --bench copy`
+
+use std::time::{Duration, Instant};
+
+use image::{GenericImage, RgbImage};
+use image_concat_rs::{
+    blits_dimensions, get_concat_blits, place_images_in_buffer, ConcatDirection, ImageBlit,
+};
+
+const ITERATIONS: u32 = 20;
+
+/// Average time of a single run of `f`
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    // warm up caches
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+/// Placement through image's generic per pixel copy, which the crate used to do
+fn place_with_copy_from(blits: &[ImageBlit<image::Rgb<u8>>]) -> RgbImage {
+    let (width, height) = blits_dimensions(blits).unwrap();
+    let mut buffer = RgbImage::new(width, height);
+    for blit in blits {
+        buffer.copy_from(blit.img, blit.x, blit.y).unwrap();
+    }
+    buffer
+}
+
+fn main() {
+    // 64 equally sized images so every copy takes the whole image fast path
+    let img = image::open("./test/1.png").unwrap().into_rgb8();
+    let imgs = vec![img; 64];
+    let megabytes = imgs.iter().map(|img| img.as_raw().len()).sum::<usize>() as f64 / 1e6;
+
+    for (name, direction) in [
+        ("vertical", ConcatDirection::Vertical),
+        ("horizontal", ConcatDirection::Horizontal),
+    ] {
+        let blits = get_concat_blits(&imgs, direction, 0, 0);
+        let generic = time(|| place_with_copy_from(&blits));
+        let slices = time(|| place_images_in_buffer(&blits).unwrap());
+
+        println!("{name}");
+        println!(
+            "  copy_from              {generic:?} ({:.0} MB/s)",
+            megabytes / generic.as_secs_f64()
+        );
+        println!(
+            "  place_images_in_buffer {slices:?} ({:.0} MB/s)",
+            megabytes / slices.as_secs_f64()
+        );
+        println!(
+            "  speedup {:.2}x",
+            generic.as_secs_f64() / slices.as_secs_f64()
+        );
+    }
+}
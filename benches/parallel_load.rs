@@ -0,0 +1,39 @@
+//! Compares serial and parallel loading of 32 images.
+//!
+//! Run with `cargo bench --features rayon --bench parallel_load`
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use image_concat_rs::{load_and_vert_concat_images, load_and_vert_concat_images_parallel};
+
+const ITERATIONS: u32 = 10;
+
+/// Average time of a single run of `f`
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    // warm up file cache and thread pool
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    // 32 images by cycling through the 8 test images
+    let img_paths: Vec<_> = (0..32)
+        .map(|i| PathBuf::from(format!("./test/{}.png", i % 8 + 1)))
+        .collect();
+
+    let serial = time(|| load_and_vert_concat_images(&img_paths).unwrap());
+    let parallel = time(|| load_and_vert_concat_images_parallel(&img_paths).unwrap());
+
+    println!("load_and_vert_concat_images          {serial:?}");
+    println!("load_and_vert_concat_images_parallel {parallel:?}");
+    println!(
+        "speedup {:.2}x",
+        serial.as_secs_f64() / parallel.as_secs_f64()
+    );
+}
@@ -0,0 +1,40 @@
+//! Compares serial and parallel placement of 256 images into one buffer.
+//!
+//! Run with `cargo bench --features rayon --bench parallel_place`
+
+use std::time::{Duration, Instant};
+
+use image_concat_rs::{get_grid_blits, place_images_in_buffer, place_images_in_buffer_parallel};
+
+const ITERATIONS: u32 = 10;
+
+/// Average time of a single run of `f`
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    // warm up thread pool
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    // 256 images by cycling through the 8 test images
+    let test_imgs: Vec<_> = (1..=8)
+        .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+        .collect();
+    let imgs: Vec<_> = (0..256).map(|i| test_imgs[i % 8].clone()).collect();
+    let blits = get_grid_blits(&imgs, 16, 0, 0);
+
+    let serial = time(|| place_images_in_buffer(&blits).unwrap());
+    let parallel = time(|| place_images_in_buffer_parallel(&blits).unwrap());
+
+    println!("place_images_in_buffer          {serial:?}");
+    println!("place_images_in_buffer_parallel {parallel:?}");
+    println!(
+        "speedup {:.2}x",
+        serial.as_secs_f64() / parallel.as_secs_f64()
+    );
+}
@@ -0,0 +1,110 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use image::codecs::gif::{GifDecoder, GifEncoder, Repeat};
+use image::{AnimationDecoder, Delay, Frame, ImageFormat, RgbaImage};
+
+use crate::{concat_images, decode_path, open_reader, ConcatDirection, ConcatError};
+
+/// Encodes a sequence of images as the frames of a looping GIF.
+///
+/// Instead of placing images side by side, each image becomes a frame shown
+/// for `frame_delay_ms`. Every frame is padded with transparency on the right
+/// and bottom to the size of the largest image rather than being resized.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load, in frame order
+/// * `out_path` - Path the GIF will be written to
+/// * `frame_delay_ms` - How long each frame is shown in milliseconds
+///
+/// # Returns
+/// * `Result<(), ConcatError>`
+/// * `ConcatError::Empty` if no paths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::images_to_gif;
+/// use std::path::PathBuf;
+/// let out_path = std::env::temp_dir().join("images_to_gif_doc.gif");
+/// let result = images_to_gif(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")], &out_path, 500);
+/// ```
+pub fn images_to_gif(
+    image_paths: &[PathBuf],
+    out_path: &Path,
+    frame_delay_ms: u32,
+) -> Result<(), ConcatError> {
+    if image_paths.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    let imgs = image_paths
+        .iter()
+        .map(|path| Ok(decode_path(path)?.into_rgba8()))
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+
+    // Every frame is drawn on a canvas the size of the largest image
+    let width = imgs.iter().map(|img| img.width()).max().unwrap_or(0);
+    let height = imgs.iter().map(|img| img.height()).max().unwrap_or(0);
+    let delay = Delay::from_numer_denom_ms(frame_delay_ms, 1);
+
+    let frames = imgs.into_iter().map(|img| {
+        let img = if img.dimensions() == (width, height) {
+            img
+        } else {
+            let mut padded = RgbaImage::new(width, height);
+            image::imageops::replace(&mut padded, &img, 0, 0);
+            padded
+        };
+        Frame::from_parts(img, 0, 0, delay)
+    });
+
+    let mut encoder = GifEncoder::new(BufWriter::new(File::create(out_path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(frames)?;
+
+    Ok(())
+}
+
+/// Loads images and concatenates them, expanding animated GIFs into each of their frames
+///
+/// Every frame of a GIF is included as an image of its own, in order, so an
+/// animation can be laid out as a strip to see it all at once. Frames are
+/// composited as they'd be displayed, each the full size of the GIF. Other
+/// images, including static GIFs, are included once. Everything is converted to
+/// RGBA so transparent frames stay transparent.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<RgbaImage, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{load_and_concat_with_gif_frames, ConcatDirection};
+/// use std::path::PathBuf;
+/// let paths = [PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")];
+/// let img_result = load_and_concat_with_gif_frames(&paths, ConcatDirection::Horizontal);
+/// ```
+pub fn load_and_concat_with_gif_frames(
+    image_paths: &[PathBuf],
+    direction: ConcatDirection,
+) -> Result<RgbaImage, ConcatError> {
+    let mut imgs = Vec::with_capacity(image_paths.len());
+    for path in image_paths {
+        let img = open_reader(path)?;
+        if img.format() == Some(ImageFormat::Gif) {
+            // Decode frames from the reader already opened instead of opening the file again
+            let frames = GifDecoder::new(img.into_inner())?.into_frames();
+            for frame in frames {
+                imgs.push(frame?.into_buffer());
+            }
+        } else {
+            imgs.push(img.decode()?.into_rgba8());
+        }
+    }
+
+    concat_images(&imgs, direction)
+}
@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+
+use bytemuck::Pod;
+use image::{ImageBuffer, PixelWithColorType, Rgba, RgbaImage};
+
+use crate::{
+    concat_images_with_options, load_and_vert_concat_images_as, overlay_watermark, Alignment,
+    Border, ConcatDirection, ConcatError, ConcatOptions, Corner, Order, Shadow, Spacing, Tint,
+};
+
+/// Step run on the concatenated image before `build` returns it
+///
+/// Bound by `Send` so builders can still be moved onto other threads.
+type Finish<P> = Box<dyn FnOnce(&mut ImageBuffer<P, Vec<<P as image::Pixel>::Subpixel>>) + Send>;
+
+/// An image queued up in a ConcatBuilder
+enum Source<P: PixelWithColorType> {
+    Image(ImageBuffer<P, Vec<P::Subpixel>>),
+    Path(PathBuf),
+}
+
+/// Chainable builder for combining concatenation options
+///
+/// Images can be added already loaded or as paths, which are loaded when the
+/// builder is built. Paths are loaded without conversion, so each image must
+/// already be in pixel type `P`.
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{Alignment, ConcatBuilder, ConcatDirection};
+/// use std::path::PathBuf;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img_result = ConcatBuilder::new()
+///     .direction(ConcatDirection::Vertical)
+///     .spacing(8)
+///     .alignment(Alignment::Center)
+///     .background(image::Rgb([255, 255, 255]))
+///     .add_image(img1)
+///     .add_path(PathBuf::from("./test/2.png"))
+///     .build();
+/// ```
+pub struct ConcatBuilder<P: PixelWithColorType> {
+    options: ConcatOptions<P>,
+    sources: Vec<Source<P>>,
+    finish: Vec<Finish<P>>,
+}
+
+impl<P> ConcatBuilder<P>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+{
+    /// Empty builder for a vertical concatenation
+    pub fn new() -> Self {
+        ConcatBuilder {
+            options: ConcatOptions::new(ConcatDirection::Vertical),
+            sources: Vec::new(),
+            finish: Vec::new(),
+        }
+    }
+
+    /// Direction images are concatenated in
+    pub fn direction(mut self, direction: ConcatDirection) -> Self {
+        self.options.direction = direction;
+        self
+    }
+
+    /// Gap inserted between adjacent images, in pixels or as a `Spacing::Percent`
+    pub fn spacing(mut self, spacing: impl Into<Spacing>) -> Self {
+        self.options.spacing = spacing.into();
+        self
+    }
+
+    /// Placement of images smaller than the largest image on the cross axis
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.options.alignment = alignment;
+        self
+    }
+
+    /// Whether the first image is placed at the start or end of the concatenation
+    pub fn order(mut self, order: Order) -> Self {
+        self.options.order = order;
+        self
+    }
+
+    /// Color of any gaps or padding
+    pub fn background(mut self, background: P) -> Self {
+        self.options.background = Some(background);
+        self
+    }
+
+    /// Line drawn over each seam between adjacent images
+    pub fn divider(mut self, width: u32, color: P) -> Self {
+        self.options.divider = Some(Border { width, color });
+        self
+    }
+
+    /// Largest output, in pixels, that may be allocated before `build` errors instead
+    pub fn max_pixels(mut self, max_pixels: u64) -> Self {
+        self.options.max_pixels = Some(max_pixels);
+        self
+    }
+
+    /// Radius in pixels of rounded corners cut into each image, showing the background
+    pub fn corner_radius(mut self, radius: u32) -> Self {
+        self.options.corner_radius = radius;
+        self
+    }
+
+    /// Soft shadow drawn behind each image, see `ConcatOptions::shadow`
+    pub fn shadow(mut self, shadow: Shadow<P>) -> Self {
+        self.options.shadow = Some(shadow);
+        self
+    }
+
+    /// Color the finished output is shifted toward by `strength`, see `apply_tint`
+    pub fn tint(mut self, color: P, strength: f32) -> Self {
+        self.options.tint = Some(Tint { color, strength });
+        self
+    }
+
+    /// Appends an already loaded image
+    pub fn add_image(mut self, img: ImageBuffer<P, Vec<P::Subpixel>>) -> Self {
+        self.sources.push(Source::Image(img));
+        self
+    }
+
+    /// Appends an image to be loaded from disk when the builder is built
+    pub fn add_path(mut self, path: PathBuf) -> Self {
+        self.sources.push(Source::Path(path));
+        self
+    }
+
+    /// Loads any queued paths and concatenates all images in the order they were added
+    ///
+    /// # Returns
+    /// * `Result<ImageBuffer, ConcatError>`
+    pub fn build(self) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+        // Take ownership of already loaded images so nothing needs to be copied before placement
+        let images = self
+            .sources
+            .into_iter()
+            .map(|source| match source {
+                Source::Image(img) => Ok(img),
+                Source::Path(path) => load_and_vert_concat_images_as(&[path]),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut img = concat_images_with_options(&images, &self.options)?;
+        for finish in self.finish {
+            finish(&mut img);
+        }
+        Ok(img)
+    }
+}
+
+impl ConcatBuilder<Rgba<u8>> {
+    /// Watermark blended into a corner of the output once it's concatenated, see `overlay_watermark`
+    ///
+    /// Can be called more than once, e.g. to mark several corners. Watermarks are
+    /// drawn in the order they were added, so later ones go on top.
+    pub fn watermark(
+        mut self,
+        watermark: RgbaImage,
+        position: Corner,
+        margin: u32,
+        opacity: f32,
+    ) -> Self {
+        self.finish.push(Box::new(move |img: &mut RgbaImage| {
+            overlay_watermark(img, &watermark, position, margin, opacity)
+        }));
+        self
+    }
+}
+
+impl<P> Default for ConcatBuilder<P>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::RgbImage;
+
+use crate::{concat_images, decode_path, open_error, ConcatDirection, ConcatError};
+
+/// Loads every file in a directory whose name matches `pattern` and concatenates them.
+///
+/// Files are ordered naturally, so numbers embedded in the name are compared by
+/// value and `10.png` comes after `2.png` rather than between `1.png` and `2.png`.
+/// Images are converted to RGB as they're loaded so any mix of formats can be used.
+///
+/// # Arguments
+/// * `dir` - Directory to search, subdirectories aren't searched
+/// * `pattern` - File name pattern where `*` matches any run of characters and `?` matches one, e.g. `"*.png"`
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no files match
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{load_and_concat_dir, ConcatDirection};
+/// use std::path::Path;
+/// let img_result = load_and_concat_dir(Path::new("./test"), "?.png", ConcatDirection::Vertical);
+/// ```
+pub fn load_and_concat_dir(
+    dir: &Path,
+    pattern: &str,
+    direction: ConcatDirection,
+) -> Result<RgbImage, ConcatError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|err| open_error(dir, err))? {
+        let entry = entry?;
+        let matches = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| glob_match(pattern, name));
+        if matches && entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    natural_sort_paths(&mut paths);
+
+    let imgs = paths
+        .iter()
+        .map(|path| Ok(decode_path(path)?.into_rgb8()))
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+
+    concat_images(&imgs, direction)
+}
+
+/// Sorts paths in natural order, comparing runs of digits by their value.
+///
+/// A plain sort compares paths character by character, which puts `img10.png`
+/// between `img1.png` and `img2.png`. Here `img2.png` comes before `img10.png`
+/// wherever the numbers appear in the path. Numbers that are equal apart from
+/// zero padding are ordered with the least padded first.
+///
+/// # Arguments
+/// * `paths` - Paths to sort in place
+///
+/// # Example
+/// ```
+/// use image_concat_rs::natural_sort_paths;
+/// use std::path::PathBuf;
+/// let mut paths = vec![PathBuf::from("img10.png"), PathBuf::from("img2.png")];
+/// natural_sort_paths(&mut paths);
+/// assert_eq!(paths, [PathBuf::from("img2.png"), PathBuf::from("img10.png")]);
+/// ```
+#[allow(clippy::ptr_arg)]
+pub fn natural_sort_paths(paths: &mut Vec<PathBuf>) {
+    paths.sort_by(|a, b| natural_cmp(a, b));
+}
+
+/// Whether `name` matches `pattern` in full, with `*` and `?` wildcards
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Position to resume from after the most recent `*`, as (pattern index, name index)
+    let mut star = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            // Let the last `*` swallow one more character and try again
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Orders paths with runs of digits compared by value, e.g. `img2` before `img10`
+fn natural_cmp(a: &Path, b: &Path) -> Ordering {
+    let a = a.to_string_lossy();
+    let b = b.to_string_lossy();
+    let (mut a, mut b) = (a.as_ref(), b.as_ref());
+
+    loop {
+        let (a_chunk, a_rest) = split_chunk(a);
+        let (b_chunk, b_rest) = split_chunk(b);
+        let ordering = match (a_chunk, b_chunk) {
+            ("", "") => return Ordering::Equal,
+            (a_chunk, b_chunk) if is_digits(a_chunk) && is_digits(b_chunk) => {
+                let a_value = a_chunk.trim_start_matches('0');
+                let b_value = b_chunk.trim_start_matches('0');
+                // Compare by value without parsing so long runs can't overflow,
+                // then put the less padded of two equal numbers first
+                a_value
+                    .len()
+                    .cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value))
+                    .then_with(|| a_chunk.len().cmp(&b_chunk.len()))
+            }
+            (a_chunk, b_chunk) => a_chunk.cmp(b_chunk),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        (a, b) = (a_rest, b_rest);
+    }
+}
+
+/// Splits off the leading run of either digits or non-digits
+fn split_chunk(s: &str) -> (&str, &str) {
+    let digits = s.starts_with(|c: char| c.is_ascii_digit());
+    let end = s
+        .find(|c: char| c.is_ascii_digit() != digits)
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn is_digits(chunk: &str) -> bool {
+    chunk.starts_with(|c: char| c.is_ascii_digit())
+}
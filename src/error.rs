@@ -0,0 +1,126 @@
+use std::fmt;
+
+/// Errors from loading, planning or placing images
+#[derive(Debug)]
+pub enum ConcatError {
+    /// An image couldn't be opened or read
+    Io(std::io::Error),
+    /// Error from the image crate while decoding, encoding or placing an image
+    Decode(image::ImageError),
+    /// No images were provided
+    Empty,
+    /// Combined dimensions of the images don't fit in a u32
+    DimensionOverflow,
+    /// Images were requested to be split into zero columns, or zero rows
+    ColumnCountZero,
+    /// Source images have different DPIs and a single one was required
+    ConflictingDpi,
+    /// An image's cross axis size differs from the first image's in a strict concatenation
+    DimensionMismatch {
+        /// Index of the offending image
+        index: usize,
+        /// Cross axis size of the first image
+        expected: u32,
+        /// Cross axis size of the offending image
+        got: u32,
+    },
+    /// The output would have more pixels than the configured limit
+    ExceedsMaxPixels {
+        /// Pixel count of the output that would have been allocated
+        pixels: u64,
+        /// Configured limit
+        max_pixels: u64,
+    },
+    /// The output couldn't be encoded within the requested number of bytes
+    ExceedsMaxBytes {
+        /// Size of the smallest encoding that was tried
+        bytes: usize,
+        /// Requested limit
+        max_bytes: usize,
+    },
+    /// An image is bigger than the bins it was to be packed into
+    TooLargeForBin {
+        /// Index of the offending image
+        index: usize,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl fmt::Display for ConcatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConcatError::Io(err) => err.fmt(f),
+            ConcatError::Decode(err) => err.fmt(f),
+            ConcatError::Empty => write!(f, "No images were provided"),
+            ConcatError::DimensionOverflow => {
+                write!(
+                    f,
+                    "Combined image dimensions exceed the maximum of {}",
+                    u32::MAX
+                )
+            }
+            ConcatError::ColumnCountZero => {
+                write!(f, "Column and row counts must be greater than 0")
+            }
+            ConcatError::ConflictingDpi => write!(f, "Source images have conflicting DPIs"),
+            ConcatError::DimensionMismatch {
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Image {index} is {got}px on the cross axis, expected {expected}px"
+            ),
+            ConcatError::ExceedsMaxPixels { pixels, max_pixels } => {
+                write!(
+                    f,
+                    "Output of {pixels} pixels exceeds the limit of {max_pixels}"
+                )
+            }
+            ConcatError::ExceedsMaxBytes { bytes, max_bytes } => {
+                write!(
+                    f,
+                    "Smallest encoding of {bytes} bytes exceeds the limit of {max_bytes}"
+                )
+            }
+            ConcatError::TooLargeForBin {
+                index,
+                width,
+                height,
+            } => write!(
+                f,
+                "Image {index} is {width}x{height}, larger than the bin size"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConcatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConcatError::Io(err) => Some(err),
+            ConcatError::Decode(err) => Some(err),
+            ConcatError::Empty
+            | ConcatError::DimensionOverflow
+            | ConcatError::ColumnCountZero
+            | ConcatError::ConflictingDpi
+            | ConcatError::DimensionMismatch { .. }
+            | ConcatError::ExceedsMaxPixels { .. }
+            | ConcatError::ExceedsMaxBytes { .. }
+            | ConcatError::TooLargeForBin { .. } => None,
+        }
+    }
+}
+
+impl From<image::ImageError> for ConcatError {
+    fn from(err: image::ImageError) -> Self {
+        ConcatError::Decode(err)
+    }
+}
+
+impl From<std::io::Error> for ConcatError {
+    fn from(err: std::io::Error) -> Self {
+        ConcatError::Io(err)
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use image::{ImageDecoder, ImageReader};
+
+/// Exif IFD pointer tag in IFD0
+const EXIF_IFD_POINTER: u16 = 0x8769;
+/// DateTimeOriginal tag in the Exif IFD
+const DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+/// Sorts paths chronologically by when each photo was taken.
+///
+/// The capture time is read from the EXIF `DateTimeOriginal` tag, falling back
+/// to the file's modification time for images without one, such as most PNGs.
+/// EXIF times have no timezone, so they're compared as if they were UTC.
+/// Paths with neither, e.g. because the file can't be read, are placed last.
+/// The sort is stable, so paths with the same time keep their relative order.
+///
+/// # Arguments
+/// * `paths` - Paths to sort in place
+///
+/// # Example
+/// ```
+/// use image_concat_rs::sort_paths_by_exif_time;
+/// use std::path::PathBuf;
+/// let mut paths = vec![PathBuf::from("./test/2.png"), PathBuf::from("./test/1.png")];
+/// sort_paths_by_exif_time(&mut paths);
+/// ```
+#[allow(clippy::ptr_arg)]
+pub fn sort_paths_by_exif_time(paths: &mut Vec<PathBuf>) {
+    // Read each time once up front rather than on every comparison
+    let mut keyed: Vec<_> = paths
+        .drain(..)
+        .map(|path| {
+            let time = exif_time(&path).or_else(|| modified_time(&path));
+            // None sorts first, so key on whether the time is missing before the time itself
+            ((time.is_none(), time), path)
+        })
+        .collect();
+    keyed.sort_by_key(|(key, _)| *key);
+    paths.extend(keyed.into_iter().map(|(_, path)| path));
+}
+
+/// `DateTimeOriginal` of the image at `path` in seconds since the unix epoch
+fn exif_time(path: &Path) -> Option<i64> {
+    let mut decoder = ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    let exif = decoder.exif_metadata().ok()??;
+    parse_timestamp(&date_time_original(&exif)?)
+}
+
+/// Modification time of the file at `path` in seconds since the unix epoch
+fn modified_time(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    match modified.duration_since(UNIX_EPOCH) {
+        Ok(since) => Some(since.as_secs() as i64),
+        Err(before) => Some(-(before.duration().as_secs() as i64)),
+    }
+}
+
+/// Finds the `DateTimeOriginal` string in a raw EXIF block, which is laid out as a TIFF file
+fn date_time_original(exif: &[u8]) -> Option<String> {
+    let big_endian = match exif.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    // Offsets come from the file, so every one is bounds checked rather than trusted
+    let read_u16 = |offset: usize| {
+        let bytes: [u8; 2] = exif.get(offset..offset.checked_add(2)?)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| {
+        let bytes: [u8; 4] = exif.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+    // Offset of the entry for `tag` in the IFD at `ifd`, each entry is 12 bytes after a u16 count
+    let find_entry = |ifd: usize, tag: u16| {
+        let count = read_u16(ifd)? as usize;
+        let entries = ifd.checked_add(2)?;
+        (0..count)
+            .map_while(|i| entries.checked_add(i * 12))
+            .find(|&entry| read_u16(entry) == Some(tag))
+    };
+
+    let ifd0 = read_u32(4)? as usize;
+    let exif_ifd = read_u32(find_entry(ifd0, EXIF_IFD_POINTER)?.checked_add(8)?)? as usize;
+    let entry = find_entry(exif_ifd, DATE_TIME_ORIGINAL)?;
+
+    // ASCII values longer than 4 bytes are stored at an offset instead of inline
+    let count = read_u32(entry.checked_add(4)?)? as usize;
+    let value = if count <= 4 {
+        entry.checked_add(8)?
+    } else {
+        read_u32(entry.checked_add(8)?)? as usize
+    };
+    let bytes = exif.get(value..value.checked_add(count)?)?;
+    let text = bytes.split(|&b| b == 0).next()?;
+    String::from_utf8(text.to_vec()).ok()
+}
+
+/// Parses an EXIF `YYYY:MM:DD HH:MM:SS` timestamp into seconds since the unix epoch
+fn parse_timestamp(timestamp: &str) -> Option<i64> {
+    let (date, time) = timestamp.trim().split_once(' ')?;
+    let mut date = date.split(':').map(|part| part.parse::<i64>().ok());
+    let mut time = time.split(':').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    // EXIF years have four digits, and bounding every field keeps the arithmetic below from
+    // overflowing on a corrupt tag. A second of 60 allows for leap seconds.
+    if !(0..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || !(0..=23).contains(&hour)
+        || !(0..=59).contains(&minute)
+        || !(0..=60).contains(&second)
+    {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Count years from March so the leap day falls at the end of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
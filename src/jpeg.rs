@@ -0,0 +1,105 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use image::error::{ImageFormatHint, UnsupportedError, UnsupportedErrorKind};
+use image::{ImageError, ImageFormat};
+
+use crate::ConcatError;
+
+/// Start of scan, the entropy coded data follows so no more headers need reading
+const SOS: u8 = 0xDA;
+/// End of image
+const EOI: u8 = 0xD9;
+/// APP14, written by Adobe software to describe how CMYK samples are stored
+const APP14: u8 = 0xEE;
+
+/// Errors if `reader` holds a plain CMYK JPEG, see `check_cmyk_jpeg_bytes`
+///
+/// Only the headers are read, and the reader is rewound to where it started
+/// afterwards so the same reader can be handed straight to the decoder.
+pub(crate) fn check_cmyk_jpeg<R: Read + Seek>(
+    reader: &mut R,
+    name: &str,
+) -> Result<(), ConcatError> {
+    let start = reader.stream_position()?;
+    let checked = check_cmyk_reader(&mut *reader, name);
+    reader.seek(SeekFrom::Start(start))?;
+    checked
+}
+
+/// Errors if `bytes` are a CMYK JPEG that would decode with inverted colors
+///
+/// The image crate converts four component JPEGs to RGB assuming the inverted
+/// CMYK that Adobe software writes alongside an APP14 marker, which covers most
+/// CMYK JPEGs in the wild. Files without the marker store plain CMYK and would
+/// come out as a negative, so they're rejected instead of silently loaded wrong.
+/// Anything that isn't a JPEG, or whose headers can't be read, is left for the
+/// decoder to handle.
+pub(crate) fn check_cmyk_jpeg_bytes(bytes: &[u8], name: &str) -> Result<(), ConcatError> {
+    check_cmyk_reader(bytes, name)
+}
+
+fn check_cmyk_reader(reader: impl Read, name: &str) -> Result<(), ConcatError> {
+    match is_plain_cmyk_jpeg(reader) {
+        Ok(true) => Err(
+            ImageError::Unsupported(UnsupportedError::from_format_and_kind(
+                ImageFormatHint::Exact(ImageFormat::Jpeg),
+                UnsupportedErrorKind::GenericFeature(format!(
+                    "CMYK without an Adobe APP14 marker in {name}, convert it to RGB first"
+                )),
+            ))
+            .into(),
+        ),
+        // A truncated or broken file gets a better error from the decoder itself
+        Ok(false) | Err(_) => Ok(()),
+    }
+}
+
+/// Whether the headers describe a four component JPEG without an Adobe APP14 marker
+fn is_plain_cmyk_jpeg(mut reader: impl Read) -> io::Result<bool> {
+    let mut soi = [0; 2];
+    reader.read_exact(&mut soi)?;
+    if soi != [0xFF, 0xD8] {
+        return Ok(false);
+    }
+
+    let mut adobe = false;
+    let mut components = 0;
+    loop {
+        let marker = read_marker(&mut reader)?;
+        match marker {
+            SOS | EOI => return Ok(components == 4 && !adobe),
+            // Restart markers and TEM stand alone without a length
+            0xD0..=0xD7 | 0x01 => continue,
+            _ => {}
+        }
+
+        let mut length = [0; 2];
+        reader.read_exact(&mut length)?;
+        let length = u16::from_be_bytes(length).saturating_sub(2) as usize;
+        let mut segment = vec![0; length];
+        reader.read_exact(&mut segment)?;
+
+        match marker {
+            APP14 => adobe |= segment.starts_with(b"Adobe"),
+            // SOF0 through SOF15, skipping DHT, JPG and DAC which share the range
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                // Precision, then height and width as u16s, then the component count
+                components = segment.get(5).copied().unwrap_or(0);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads the next marker code, skipping any 0xFF fill bytes before it
+fn read_marker(reader: &mut impl Read) -> io::Result<u8> {
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+    if byte[0] != 0xFF {
+        return Err(io::ErrorKind::InvalidData.into());
+    }
+    while byte[0] == 0xFF {
+        reader.read_exact(&mut byte)?;
+    }
+    Ok(byte[0])
+}
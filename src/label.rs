@@ -0,0 +1,243 @@
+use ab_glyph::{point, Font, PxScale, ScaleFont};
+use image::{GenericImage, ImageBuffer, Pixel};
+use num_traits::NumCast;
+
+use crate::{grid_cell_sizes, ConcatError, FillOrder};
+
+/// An image and the caption drawn beneath it
+pub type LabeledImage<P> = (ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>, String);
+
+/// How captions are rendered by `label_concat_images`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CaptionStyle<P: Pixel> {
+    /// Font size in pixels, which also determines the height of the caption strip
+    pub size: f32,
+    /// Color of the caption text
+    pub color: P,
+    /// Fill for the caption strips and any space not covered by an image
+    pub background: P,
+}
+
+/// Concatenates images into a grid with a caption beneath each image
+///
+/// Images are laid out like `grid_concat_images`, with a caption strip added below
+/// every row. The strip is as tall as a line of text in `font` at `style.size`.
+/// Captions start at the left edge of their cell and are cut off at its right edge.
+///
+/// Requires the `text` feature.
+///
+/// # Arguments
+/// * `images_with_labels` - Slice of ImageBuffers and the caption to draw beneath each
+/// * `columns` - Number of images per row
+/// * `font` - Font to render captions with, e.g. an `ab_glyph::FontRef`
+/// * `style` - Size and colors of the captions
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::ab_glyph::FontRef;
+/// use image_concat_rs::{label_concat_images, CaptionStyle};
+/// let font_data = std::fs::read("./test/DejaVuSansMono.ttf").unwrap();
+/// let font = FontRef::try_from_slice(&font_data).unwrap();
+/// let labeled: Vec<_> = (1..=4)
+///     .map(|i| (image::open(format!("./test/{i}.png")).unwrap().into_rgb8(), format!("{i}.png")))
+///     .collect();
+/// let style = CaptionStyle {
+///     size: 16.0,
+///     color: image::Rgb([0, 0, 0]),
+///     background: image::Rgb([255, 255, 255]),
+/// };
+/// let img_result = label_concat_images(&labeled, 2, &font, &style);
+/// ```
+pub fn label_concat_images<P: Pixel>(
+    images_with_labels: &[LabeledImage<P>],
+    columns: usize,
+    font: &impl Font,
+    style: &CaptionStyle<P>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images_with_labels.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let font = font.as_scaled(PxScale::from(style.size));
+    let strip_height = font.height().ceil() as u32;
+
+    // Same cells as grid_concat_images with every row made taller by a caption strip
+    let dimensions = images_with_labels.iter().map(|(img, _)| img.dimensions());
+    let (col_widths, mut row_heights) = grid_cell_sizes(dimensions, columns, FillOrder::RowMajor);
+    for height in &mut row_heights {
+        *height = height
+            .checked_add(strip_height)
+            .ok_or(ConcatError::DimensionOverflow)?;
+    }
+
+    // Running sums of the cell sizes give us each cell's origin, with the total size at the end
+    let offsets = |sizes: &[u32]| {
+        sizes.iter().try_fold(vec![0u32], |mut offsets, size| {
+            offsets.push(offsets.last()?.checked_add(*size)?);
+            Some(offsets)
+        })
+    };
+    let col_xs = offsets(&col_widths).ok_or(ConcatError::DimensionOverflow)?;
+    let row_ys = offsets(&row_heights).ok_or(ConcatError::DimensionOverflow)?;
+
+    let mut buffer = ImageBuffer::from_pixel(
+        *col_xs.last().unwrap(),
+        *row_ys.last().unwrap(),
+        style.background,
+    );
+
+    for (idx, (img, label)) in images_with_labels.iter().enumerate() {
+        let (row, col) = (idx / columns, idx % columns);
+        let (x, y) = (col_xs[col], row_ys[row]);
+        buffer.copy_from(img, x, y)?;
+
+        let strip_top = row_ys[row + 1] - strip_height;
+        draw_caption(
+            &mut buffer,
+            &font,
+            label,
+            (x, strip_top),
+            col_xs[col + 1],
+            style.color,
+        );
+    }
+
+    Ok(buffer)
+}
+
+/// Adds a banner above an image with a title centered in it, e.g. to label a report montage
+///
+/// The output is `height` pixels taller than `img`, which is placed below the
+/// strip untouched. The title is rendered at three quarters of the strip's
+/// height so it has a little room above and below, and a title too wide for
+/// the image starts at its left edge and is cut off at the right.
+///
+/// Requires the `text` feature.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to add the strip above
+/// * `title` - Text to center in the strip
+/// * `height` - Height of the strip in pixels
+/// * `font` - Font to render the title with, e.g. an `ab_glyph::FontRef`
+/// * `background` - Fill for the strip
+/// * `color` - Color of the title text
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::ab_glyph::FontRef;
+/// use image_concat_rs::{add_title_strip, concat_images, ConcatDirection};
+/// let font_data = std::fs::read("./test/DejaVuSansMono.ttf").unwrap();
+/// let font = FontRef::try_from_slice(&font_data).unwrap();
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let montage = concat_images(&[img1, img2], ConcatDirection::Vertical).unwrap();
+/// let white = image::Rgb([255, 255, 255]);
+/// let img_result = add_title_strip(&montage, "Scores", 24, &font, white, image::Rgb([0, 0, 0]));
+/// ```
+pub fn add_title_strip<P: Pixel>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    title: &str,
+    height: u32,
+    font: &impl Font,
+    background: P,
+    color: P,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let total_height = img
+        .height()
+        .checked_add(height)
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let mut buffer = ImageBuffer::from_pixel(img.width(), total_height, background);
+    buffer.copy_from(img, 0, height)?;
+
+    let font = font.as_scaled(PxScale::from(height as f32 * 0.75));
+    let x = ((img.width() as f32 - line_width(&font, title)) / 2.0)
+        .max(0.0)
+        .round();
+    let y = ((height as f32 - font.height()) / 2.0).max(0.0).round();
+    draw_caption(
+        &mut buffer,
+        &font,
+        title,
+        (x as u32, y as u32),
+        img.width(),
+        color,
+    );
+
+    Ok(buffer)
+}
+
+/// Width of `text` drawn on a single line, including kerning
+fn line_width<F: Font>(font: &impl ScaleFont<F>, text: &str) -> f32 {
+    let mut previous = None;
+    text.chars()
+        .map(|c| {
+            let glyph_id = font.glyph_id(c);
+            let kern = previous.map_or(0.0, |previous| font.kern(previous, glyph_id));
+            previous = Some(glyph_id);
+            kern + font.h_advance(glyph_id)
+        })
+        .sum()
+}
+
+/// Draws a single line of text with its top left at `origin`, clipped to `right` and `font.height()`
+fn draw_caption<P: Pixel, F: Font>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    font: &impl ScaleFont<F>,
+    text: &str,
+    (x, y): (u32, u32),
+    right: u32,
+    color: P,
+) {
+    let bottom = (y as f32 + font.height()).ceil();
+    let mut caret = point(x as f32, y as f32 + font.ascent());
+    let mut previous = None;
+
+    for c in text.chars() {
+        let glyph_id = font.glyph_id(c);
+        if let Some(previous) = previous {
+            caret.x += font.kern(previous, glyph_id);
+        }
+        previous = Some(glyph_id);
+
+        let glyph = glyph_id.with_scale_and_position(font.scale(), caret);
+        caret.x += font.h_advance(glyph_id);
+
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|glyph_x, glyph_y, coverage| {
+            let pixel_x = bounds.min.x + glyph_x as f32;
+            let pixel_y = bounds.min.y + glyph_y as f32;
+            if pixel_x < x as f32
+                || pixel_x >= right as f32
+                || pixel_y < y as f32
+                || pixel_y >= bottom
+            {
+                return;
+            }
+
+            let Some(pixel) = buffer.get_pixel_mut_checked(pixel_x as u32, pixel_y as u32) else {
+                return;
+            };
+            *pixel = pixel.map2(&color, |background, text| blend(background, text, coverage));
+        });
+    }
+}
+
+/// Linear blend from `background` to `text` by glyph `coverage`
+fn blend<T: image::Primitive>(background: T, text: T, coverage: f32) -> T {
+    let background: f32 = NumCast::from(background).unwrap_or_default();
+    let text: f32 = NumCast::from(text).unwrap_or_default();
+    NumCast::from(background + (text - background) * coverage.clamp(0.0, 1.0))
+        .unwrap_or_else(T::zero)
+}
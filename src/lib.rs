@@ -1,8 +1,51 @@
+use std::borrow::Cow;
 use std::cmp::max;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use image::io::Reader as ImageReader;
-use image::{GenericImage, ImageBuffer, ImageDecoder, Pixel, RgbImage};
+use bytemuck::Pod;
+use image::codecs::jpeg::JpegEncoder;
+use image::error::{ParameterError, ParameterErrorKind};
+use image::imageops::{self, FilterType};
+use image::{
+    ColorType, DynamicImage, EncodableLayout, ExtendedColorType, GenericImage, GenericImageView,
+    ImageBuffer, ImageDecoder, ImageFormat, ImageReader, Pixel, PixelWithColorType, Rgb, RgbImage,
+    RgbaImage,
+};
+use num_traits::ToPrimitive;
+
+mod animation;
+mod builder;
+mod dir;
+mod error;
+mod exif;
+mod jpeg;
+#[cfg(feature = "text")]
+mod label;
+mod metadata;
+mod pack;
+#[cfg(feature = "rayon")]
+mod semaphore;
+mod stream;
+
+/// Font loading for captions, re-exported so callers use the same version
+#[cfg(feature = "text")]
+pub use ab_glyph;
+pub use animation::{images_to_gif, load_and_concat_with_gif_frames};
+pub use builder::ConcatBuilder;
+pub use dir::{load_and_concat_dir, natural_sort_paths};
+pub use error::ConcatError;
+pub use exif::sort_paths_by_exif_time;
+use jpeg::{check_cmyk_jpeg, check_cmyk_jpeg_bytes};
+#[cfg(feature = "text")]
+pub use label::{add_title_strip, label_concat_images, CaptionStyle, LabeledImage};
+pub use metadata::save_with_metadata;
+pub use pack::{pack_bins, PackedBin, PlacedRect};
+pub use stream::stream_vert_concat_to_png;
 
 /// Loads given images and vertically concatenates them.
 /// Images are directly decoded into a single ImageBuffer to avoid unnecessary copying.
@@ -12,6 +55,7 @@ use image::{GenericImage, ImageBuffer, ImageDecoder, Pixel, RgbImage};
 ///
 /// # Returns
 /// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
 ///
 /// # Example
 /// ```
@@ -21,288 +65,917 @@ use image::{GenericImage, ImageBuffer, ImageDecoder, Pixel, RgbImage};
 /// // or
 /// let img_result = load_and_vert_concat_images(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
 /// ```
-pub fn load_and_vert_concat_images(image_paths: &[PathBuf]) -> Result<RgbImage, image::ImageError> {
-    let mut total_height = 0;
-    let mut max_width = 0;
-
-    // Loop through images creating decoders w/o actually reading the images yet
-    let mut decoders = Vec::new();
-    for path in image_paths {
-        let img = ImageReader::open(path).map_err(|err| {
-            std::io::Error::new(
-                err.kind(),
-                format!("Error opening image {}: {}", path.to_str().unwrap(), err),
-            )
-        })?;
-
-        let decoder = img.into_decoder()?;
-
-        // Track dimensions so we can pre-allocate an ImageBuffer to contain all images
-        let (width, height) = decoder.dimensions();
-        total_height += height;
-        max_width = max(max_width, width);
-
-        decoders.push(decoder);
-    }
-
-    // Make an image buffer large enough to contain all images
-    let mut buffer: RgbImage = ImageBuffer::new(max_width, total_height);
-
-    // Loop through decoders, decoding directly into ImageBuffer
-    let mut byte_start: u64 = 0;
-    for decoder in decoders {
-        let byte_len = decoder.total_bytes();
-        let byte_end = byte_start + byte_len;
+pub fn load_and_vert_concat_images(image_paths: &[PathBuf]) -> Result<RgbImage, ConcatError> {
+    load_and_vert_concat_images_as(image_paths)
+}
 
-        // Target portion of buffer for n-th image
-        let slice = buffer
-            .get_mut(byte_start as usize..byte_end as usize)
-            .unwrap();
+/// Loads given images and vertically concatenates them into an ImageBuffer of pixel type `P`.
+/// Images are directly decoded into a single ImageBuffer to avoid unnecessary copying.
+///
+/// Since images are decoded as is, every image's native color type must match `P`,
+/// e.g. `Rgba<u8>` requires 8-bit RGBA sources. No conversion is performed and an
+/// error is returned for the first image that doesn't match.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `ImageBuffer<P, Vec<P::Subpixel>>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_as;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_as::<image::Rgb<u8>>(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+pub fn load_and_vert_concat_images_as<P>(
+    image_paths: &[PathBuf],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+{
+    vert_concat_decoders(image_paths, open_decoder::<P>, |_, _| {})
+}
 
-        // Decode image into buffer slice
-        let _ = decoder.read_image(slice);
+/// Loads given images, applying their EXIF orientation, and vertically concatenates them.
+///
+/// Phone photos are often stored sideways with an EXIF tag describing how to
+/// rotate or flip them for display. This applies that tag so images appear the
+/// right way up. Since the rotated image can't be decoded straight into the final
+/// buffer, each image is decoded on its own, converted to RGB and then copied.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_oriented;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_oriented(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+pub fn load_and_vert_concat_images_oriented(
+    image_paths: &[PathBuf],
+) -> Result<RgbImage, ConcatError> {
+    let imgs = image_paths
+        .iter()
+        .map(|path| {
+            let img = open_reader(path)?;
+            let mut decoder = img.into_decoder()?;
+            let orientation = decoder.orientation()?;
 
-        byte_start = byte_end;
-    }
+            let mut img = DynamicImage::from_decoder(decoder)?;
+            img.apply_orientation(orientation);
+            Ok(img.into_rgb8())
+        })
+        .collect::<Result<Vec<_>, ConcatError>>()?;
 
-    // Return concatenated images
-    Ok(buffer)
+    concat_images(&imgs, ConcatDirection::Vertical)
 }
 
-/// Loads given images and concatenate them into columns.
-/// Images are directly decoded into vertical columns to avoid unnecessary copying,
-/// but horizontal concatenation of those columns requires copying of already decoded images.
+/// Loads given images, trimming uniform borders from each, and vertically concatenates them.
+///
+/// Each image is run through `autocrop` before concatenating, e.g. to strip the
+/// white margins of scanned pages so they butt up against each other. Like
+/// `load_and_vert_concat_images_oriented`, each image is decoded on its own,
+/// converted to RGB and then copied.
 ///
 /// # Arguments
 /// * `image_paths` - Slice of PathBufs to images to load
-/// * `columns` - number of columns to split images into
+/// * `bg` - Border color to remove
+/// * `tolerance` - Largest per channel difference from `bg` still treated as border
 ///
 /// # Returns
 /// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
 ///
 /// # Example
 /// ```
-/// use image_concat_rs::load_and_column_concat_images;
+/// use image_concat_rs::load_and_vert_concat_images_autocropped;
 /// use std::path::PathBuf;
-/// let img_result = load_and_column_concat_images(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")], 2);
+/// let img_result = load_and_vert_concat_images_autocropped(
+///     &[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")],
+///     image::Rgb([255, 255, 255]),
+///     8.0,
+/// );
 /// ```
-pub fn load_and_column_concat_images(
+pub fn load_and_vert_concat_images_autocropped(
     image_paths: &[PathBuf],
-    columns: usize,
-) -> Result<RgbImage, image::ImageError> {
-    // Vertical concatenation is more performant than horizontal because we can use the contiguous
-    // nature of the memory to directly decode images into a final buffer one after another without
-    // making copies of data. Horitontal concatenation would require decoding one row of each image
-    // into a final buffer before moving to the next line which I don't see a way to do in ImageDecoder.
-    // As such, we'll performantly vertical concatenate columns of images and then horizontally
-    // concatenate the columns into a single image buffer.
-    // Unfortunately, the horizontal concatenation will require explicitly copying memory over.
-
-    // vec to store our vertically concatenated columns
-    let mut col_buffs = Vec::new();
+    bg: Rgb<u8>,
+    tolerance: f64,
+) -> Result<RgbImage, ConcatError> {
+    let imgs = image_paths
+        .iter()
+        .map(|path| {
+            let img = decode_path(path)?.into_rgb8();
+            Ok(autocrop(&img, bg, tolerance))
+        })
+        .collect::<Result<Vec<_>, ConcatError>>()?;
 
-    // Max number of images per column
-    let chunk_size = image_paths.len() / columns;
-    // Starting index of columns that will have less images
-    let chunk_remainder = image_paths.len() % columns;
+    concat_images(&imgs, ConcatDirection::Vertical)
+}
 
-    // Build image columns
-    let mut start = 0;
-    for idx in 0..columns {
-        // Determine if this is a full size column or a partial column
-        let chunk_size = if idx < chunk_remainder {
-            chunk_size + 1
-        } else {
-            chunk_size
-        };
-        let end = start + chunk_size;
+/// Loads given 16-bit images and vertically concatenates them without losing precision.
+/// Images are directly decoded into a single ImageBuffer like `load_and_vert_concat_images`.
+///
+/// Every image must be 16-bit RGB, e.g. a 16-bit PNG or TIFF, see `load_and_vert_concat_images_as`.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `ImageBuffer<Rgb<u16>, Vec<u16>>`
+/// * `ConcatError::Empty` if no paths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_u16;
+/// use std::path::PathBuf;
+/// let path = std::env::temp_dir().join("load_and_vert_concat_images_u16_doc.png");
+/// image::ImageBuffer::from_pixel(4, 4, image::Rgb([u16::MAX, 0, 0])).save(&path).unwrap();
+/// let img_result = load_and_vert_concat_images_u16(&[path.clone(), path]);
+/// ```
+pub fn load_and_vert_concat_images_u16(
+    image_paths: &[PathBuf],
+) -> Result<ImageBuffer<Rgb<u16>, Vec<u16>>, ConcatError> {
+    load_and_vert_concat_images_as(image_paths)
+}
 
-        // Grab dynamic chunk size of images and concat verically
-        let buff = load_and_vert_concat_images(&image_paths[start..end])?;
-        col_buffs.push(buff);
+/// Loads given images and vertically concatenates them into the narrowest pixel type that fits them all
+///
+/// Each image's header is read first to find its color type, and the output
+/// type is picked the same way as `concat_dynamic_images`, so all grayscale
+/// inputs give a `GrayImage` and any input with alpha gives an image with alpha.
+/// When every image is already in that type they're decoded straight into a
+/// single buffer like `load_and_vert_concat_images_as`, from the decoders that
+/// read the headers. Otherwise each image is decoded on its own and converted,
+/// which requires a copy of every image.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `Result<DynamicImage, ConcatError>`
+/// * `ConcatError::Empty` if no paths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_auto;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_auto(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+pub fn load_and_vert_concat_images_auto(
+    image_paths: &[PathBuf],
+) -> Result<DynamicImage, ConcatError> {
+    let decoders = image_paths
+        .iter()
+        .map(|path| Ok(open_reader(path)?.into_decoder()?))
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+    if decoders.is_empty() {
+        return Err(ConcatError::Empty);
+    }
 
-        start = end;
+    let target = widest_format(decoders.iter().map(|decoder| decoder.color_type()));
+    if decoders
+        .iter()
+        .any(|decoder| decoder.color_type() != target.color_type())
+    {
+        let imgs = decoders
+            .into_iter()
+            .map(DynamicImage::from_decoder)
+            .collect::<Result<Vec<_>, _>>()?;
+        return concat_heterogeneous(&imgs, ConcatDirection::Vertical, target);
     }
 
-    concat_images(&col_buffs, ConcatDirection::Horizontal)
-}
+    // Everything already matches, so decode straight into one buffer
+    let img = match target {
+        PixelFormat::Luma8 => decode_vert_concat::<image::Luma<u8>, _>(decoders, |_, _| {})?.into(),
+        PixelFormat::LumaA8 => {
+            decode_vert_concat::<image::LumaA<u8>, _>(decoders, |_, _| {})?.into()
+        }
+        PixelFormat::Rgb8 => decode_vert_concat::<Rgb<u8>, _>(decoders, |_, _| {})?.into(),
+        PixelFormat::Rgba8 => decode_vert_concat::<image::Rgba<u8>, _>(decoders, |_, _| {})?.into(),
+        PixelFormat::Luma16 => {
+            decode_vert_concat::<image::Luma<u16>, _>(decoders, |_, _| {})?.into()
+        }
+        PixelFormat::LumaA16 => {
+            decode_vert_concat::<image::LumaA<u16>, _>(decoders, |_, _| {})?.into()
+        }
+        PixelFormat::Rgb16 => decode_vert_concat::<Rgb<u16>, _>(decoders, |_, _| {})?.into(),
+        PixelFormat::Rgba16 => {
+            decode_vert_concat::<image::Rgba<u16>, _>(decoders, |_, _| {})?.into()
+        }
+        PixelFormat::Rgb32F => decode_vert_concat::<Rgb<f32>, _>(decoders, |_, _| {})?.into(),
+        PixelFormat::Rgba32F => {
+            decode_vert_concat::<image::Rgba<f32>, _>(decoders, |_, _| {})?.into()
+        }
+    };
 
-pub enum ConcatDirection {
-    Vertical,
-    Horizontal,
+    Ok(img)
 }
 
-/// Concatenates ImageBuffers vertically or horizontally
+/// Loads images from an iterator of paths and vertically concatenates them
+/// like `load_and_vert_concat_images`.
+///
+/// Paths are consumed as they're yielded, so a directory walk can be passed in
+/// without collecting it first. Every decoder is opened before any decoding
+/// starts to size the buffer, so each file is held open until the end.
 ///
 /// # Arguments
-/// * `images` - Slice of ImageBuffers to concatenate
-/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `image_paths` - Iterator of PathBufs to images to load
 ///
 /// # Returns
-/// * `Result<ImageBuffer, image::ImageError>`
+/// * `RgbImage`
+/// * `ConcatError::Empty` if the iterator yields no paths
 ///
 /// # Example
 /// ```
-/// use image_concat_rs::{concat_images, ConcatDirection};
-/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
-/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
-/// let img_result = concat_images(&[img1,img2], ConcatDirection::Vertical);
+/// use image_concat_rs::load_and_vert_concat_images_iter;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_iter((1..=2).map(|i| PathBuf::from(format!("./test/{i}.png"))));
 /// ```
-pub fn concat_images<P: Pixel>(
-    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
-    direction: ConcatDirection,
-) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, image::ImageError> {
-    let blits = get_concat_blits(images, direction, 0, 0);
-    place_images_in_buffer(&blits)
+pub fn load_and_vert_concat_images_iter<I: IntoIterator<Item = PathBuf>>(
+    image_paths: I,
+) -> Result<RgbImage, ConcatError> {
+    vert_concat_decoders(image_paths, open_decoder::<Rgb<u8>>, |_, _| {})
 }
 
-pub struct ImageBlit<'a, P: Pixel> {
-    pub img: &'a ImageBuffer<P, Vec<P::Subpixel>>,
-    pub x: u32,
-    pub y: u32,
-    // TODO could probably add origin pretty easily.
-    // - One complication that comes to mind is a non top left origin on left or
-    //   top boundary would cause the image buffer to grow to accomodate which
-    //   would then offset all other image placements. Would need to add logic
-    //   to clip images probably.
+/// Loads given images and vertically concatenates them, reporting progress as each image is decoded.
+/// Images are directly decoded into a single ImageBuffer like `load_and_vert_concat_images`.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `callback` - Called with `(completed, total)` after each image is decoded
+///
+/// # Returns
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_with_progress;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_with_progress(
+///     &[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")],
+///     |completed, total| println!("Decoded {completed}/{total}"),
+/// );
+/// ```
+pub fn load_and_vert_concat_images_with_progress(
+    image_paths: &[PathBuf],
+    callback: impl FnMut(usize, usize),
+) -> Result<RgbImage, ConcatError> {
+    vert_concat_decoders(image_paths, open_decoder::<Rgb<u8>>, callback)
 }
 
-/// Places ImageBuffers into a single buffer
-///   
-/// The list of images and placements will be scanned to determine the total size
-/// of the buffer then all images will be copied into the buffer.
+/// A concatenated image and the images that were skipped, with why each couldn't be loaded
+pub type ImageWithFailures = (RgbImage, Vec<(PathBuf, ConcatError)>);
+
+/// Loads given images and vertically concatenates them, skipping any that fail to load.
 ///
-/// The goal of this function is to enable direction agnostic concatenation with
-/// as few copies as possible. For example, instead of doing column concatenation
-/// by creating a column of images and then horizontally concatenating them,
-/// which would require an unnecessary copy of the columns into the final
-/// horizontal alignment, this takes all the desired placements and copies them
-/// into a single buffer.
+/// Batch jobs over user supplied folders often hit the odd corrupt or missing
+/// file. Rather than failing the whole concatenation, each failure is collected
+/// with its path and the remaining images are concatenated in order. Like
+/// `load_and_vert_concat_images_oriented`, each image is decoded on its own,
+/// converted to RGB and then copied, so a bad file is found before anything is
+/// placed.
 ///
 /// # Arguments
-/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
-///  target coordinate to place the top left of the image
+/// * `image_paths` - Slice of PathBufs to images to load
 ///
 /// # Returns
-/// * `ImageBuffer` - Single ImageBuffer containing all images
+/// * `(RgbImage, Vec<(PathBuf, ConcatError)>)` - Image of everything that loaded and the paths that didn't
+/// * `ConcatError::Empty` if no image could be loaded
 ///
 /// # Example
 /// ```
-/// use image_concat_rs::{place_images_in_buffer,ImageBlit};
-/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
-/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
-/// let img_result = place_images_in_buffer(&[ImageBlit{img: &img1, x: 0, y: 0}, ImageBlit{img: &img2, x: img1.width(), y: 0}]);
+/// use image_concat_rs::load_and_vert_concat_images_skip_errors;
+/// use std::path::PathBuf;
+/// let (img, failures) = load_and_vert_concat_images_skip_errors(
+///     &[PathBuf::from("./test/1.png"), PathBuf::from("./test/missing.png")],
+/// ).unwrap();
+/// for (path, err) in &failures {
+///     eprintln!("Skipped {}: {err}", path.display());
+/// }
 /// ```
-pub fn place_images_in_buffer<P: Pixel>(
-    images: &[ImageBlit<P>],
-) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, image::ImageError> {
-    // Each each images start point and dimensions to determine the total buffer size we'll need to contain everything
-    let (total_width, total_height) =
-        images.iter().fold((0, 0), |(max_width, max_height), blit| {
-            (
-                max(max_width, blit.x + blit.img.width()),
-                max(max_height, blit.y + blit.img.height()),
-            )
-        });
-
-    // Create an image buffer large enough to contain all images
-    let mut buffer = ImageBuffer::new(total_width, total_height);
-
-    // Copy each image into the final buffer
-    for blit in images {
-        buffer.copy_from(blit.img, blit.x, blit.y)?;
+pub fn load_and_vert_concat_images_skip_errors(
+    image_paths: &[PathBuf],
+) -> Result<ImageWithFailures, ConcatError> {
+    let mut imgs = Vec::new();
+    let mut failures = Vec::new();
+    for path in image_paths {
+        let loaded = decode_path(path).map(DynamicImage::into_rgb8);
+        match loaded {
+            Ok(img) => imgs.push(img),
+            Err(err) => failures.push((path.clone(), err)),
+        }
     }
 
-    Ok(buffer)
+    let img = concat_images(&imgs, ConcatDirection::Vertical)?;
+    Ok((img, failures))
 }
 
-/// Creates a Vector of ImageBlit structs
+/// Loads given images like `load_and_vert_concat_images`, retrying transient failures to read them.
 ///
-/// Takes start location and concat direction to create blits that will vertically or horizontally cocnatenate ImageBuffers
+/// Files on network filesystems can fail to open or read with errors that clear
+/// up on their own. Each file is read into memory in a single attempt, and an
+/// attempt failing with `io::ErrorKind::Interrupted` or `io::ErrorKind::TimedOut`
+/// is retried up to `retries` times, waiting `backoff` before the first retry
+/// and twice as long before each one after. Any other error, or the last retry
+/// failing, is returned as is. Since every file is buffered before decoding,
+/// this needs memory for all the encoded images on top of the output.
 ///
 /// # Arguments
-/// * `images` - Slice of ImageBuffers to concatenate
-/// * `concat_direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
-/// * `start_y` - y coord that the origin of the first image will be placed
-/// * `start_x` - x coord that the origin of the first image will be placed
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `retries` - Times to retry reading each file after a transient failure
+/// * `backoff` - Wait before the first retry, doubled for each retry after
 ///
 /// # Returns
-/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
 ///
 /// # Example
 /// ```
-/// use image_concat_rs::{get_concat_blits, ConcatDirection};
-/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
-/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
-/// let blits = get_concat_blits(&[img1,img2], ConcatDirection::Vertical, 0, 0);
+/// use image_concat_rs::load_with_retry;
+/// use std::path::PathBuf;
+/// use std::time::Duration;
+/// let paths = [PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")];
+/// let img_result = load_with_retry(&paths, 3, Duration::from_millis(100));
 /// ```
-pub fn get_concat_blits<P: Pixel>(
-    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
-    concat_direction: ConcatDirection,
-    start_x: u32,
-    start_y: u32,
-) -> Vec<ImageBlit<P>> {
-    // Strep through each image and create an ImageBlit with start relative to the previous image's width or height depending on the concat direction
-    let (blits, _) = images.iter().fold(
-        (Vec::new(), (start_x, start_y)),
-        |(mut blits, (x, y)), img| {
-            let blit = ImageBlit { img, x, y };
-            blits.push(blit);
-            match concat_direction {
-                ConcatDirection::Vertical => (blits, (x, y + img.height())),
-                ConcatDirection::Horizontal => (blits, (x + img.width(), y)),
-            }
-        },
-    );
+pub fn load_with_retry(
+    image_paths: &[PathBuf],
+    retries: u32,
+    backoff: Duration,
+) -> Result<RgbImage, ConcatError> {
+    load_with_retry_from(image_paths, retries, backoff, |path| {
+        std::fs::File::open(path)
+    })
+}
 
-    blits
+/// `load_with_retry` reading each file from whatever `open` returns, so tests can fail on cue
+fn load_with_retry_from<R: Read>(
+    image_paths: &[PathBuf],
+    retries: u32,
+    backoff: Duration,
+    open: impl Fn(&Path) -> std::io::Result<R>,
+) -> Result<RgbImage, ConcatError> {
+    let open_decoder = |path: &Path| {
+        let bytes = retry_transient(retries, backoff, || {
+            let mut bytes = Vec::new();
+            open(path)?.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        })
+        .map_err(|err| open_error(path, err))?;
+
+        check_cmyk_jpeg_bytes(&bytes, &path.display().to_string())?;
+        let img = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+        check_color_type::<Rgb<u8>, _>(img.into_decoder()?, path)
+    };
+    vert_concat_decoders(image_paths, open_decoder, |_, _| {})
 }
 
-/// Concatenates images into columns
-///
-/// This will take already loaded images and concatenate them in vertical columns.
+/// Runs `attempt`, retrying it up to `retries` times with exponential backoff while it fails transiently
+fn retry_transient<T>(
+    retries: u32,
+    backoff: Duration,
+    mut attempt: impl FnMut() -> std::io::Result<T>,
+) -> std::io::Result<T> {
+    let mut retried = 0;
+    loop {
+        match attempt() {
+            Err(err)
+                if retried < retries
+                    && matches!(
+                        err.kind(),
+                        std::io::ErrorKind::Interrupted | std::io::ErrorKind::TimedOut
+                    ) =>
+            {
+                std::thread::sleep(backoff.saturating_mul(2u32.saturating_pow(retried)));
+                retried += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Loads given images through memory maps and vertically concatenates them.
 ///
-/// Given a desired number of columns, it will divde them as evenly as possible,
-/// placing what will evenly divide into all columns and spreading the remainders
-/// across the front columns.
+/// Same as `load_and_vert_concat_images`, but each file is memory mapped rather
+/// than read through buffered IO, letting the OS page in very large sources such
+/// as gigapixel TIFFs on demand with fewer syscalls.
 ///
-/// The order is currently top to bottom, moving to the next column from left to right.
-/// This order might change as it makes knowing where empty rows are a bit unintuitive.
+/// Requires the `mmap` feature. As with any memory map, files must not be
+/// modified or truncated by another process while they are being loaded.
 ///
 /// # Arguments
-/// * `images` - Slice of ImageBuffers to concatenate in columns
-/// * `columns` - Number of columns to split images into
+/// * `image_paths` - Slice of PathBufs to images to load
 ///
 /// # Returns
-/// * `Result<ImageBuffer, image::ImageError>`
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
 ///
 /// # Example
 /// ```
-/// use image_concat_rs::{column_concat_images, ConcatDirection};
-/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
-/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
-/// let img_result = column_concat_images(&[img1,img2], 2);
+/// use image_concat_rs::load_and_vert_concat_images_mmap;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_mmap(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+#[cfg(feature = "mmap")]
+pub fn load_and_vert_concat_images_mmap(image_paths: &[PathBuf]) -> Result<RgbImage, ConcatError> {
+    load_and_vert_concat_images_mmap_as(image_paths)
+}
+
+/// Loads given images through memory maps and vertically concatenates them into an ImageBuffer of pixel type `P`.
+///
+/// See `load_and_vert_concat_images_mmap` and `load_and_vert_concat_images_as`.
+///
+/// Requires the `mmap` feature.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `ImageBuffer<P, Vec<P::Subpixel>>`
 ///
+/// # Example
 /// ```
-pub fn column_concat_images<P: Pixel>(
-    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
-    columns: usize,
-) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, image::ImageError> {
-    let num_images = images.len();
+/// use image_concat_rs::load_and_vert_concat_images_mmap_as;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_mmap_as::<image::Rgb<u8>>(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+#[cfg(feature = "mmap")]
+pub fn load_and_vert_concat_images_mmap_as<P>(
+    image_paths: &[PathBuf],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+{
+    vert_concat_decoders(image_paths, open_mmap_decoder::<P>, |_, _| {})
+}
 
-    // Max number of images per column
-    let chunk_size = num_images / columns;
-    // Starting index of columns that will have less images
-    let chunk_remainder = num_images % columns;
-    // create blank image the size of the first column
-    let blank_col = ImageBuffer::new(images[0].width(), images[0].height());
+/// Shared implementation of the direct decoding loaders, opening each path with `open`
+/// and calling `callback` after each decode
+fn vert_concat_decoders<P, D>(
+    image_paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    open: impl Fn(&Path) -> Result<D, ConcatError>,
+    callback: impl FnMut(usize, usize),
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+    D: ImageDecoder,
+{
+    // Loop through images creating decoders w/o actually reading the images yet
+    let decoders = image_paths
+        .into_iter()
+        .map(|path| open(path.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    decode_vert_concat(decoders, callback)
+}
 
-    // vec of ImageBlit instructions we will execute all at once after planning the columns
-    let mut blits = Vec::with_capacity(num_images);
+/// Decodes already opened decoders straight into one vertically concatenated buffer,
+/// calling `callback` after each decode. Every decoder must have the color type of `P`.
+fn decode_vert_concat<P, D>(
+    decoders: Vec<D>,
+    mut callback: impl FnMut(usize, usize),
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+    D: ImageDecoder,
+{
+    if decoders.is_empty() {
+        return Err(ConcatError::Empty);
+    }
 
-    // Build column image blits
+    // Track dimensions so we can pre-allocate an ImageBuffer to contain all images
+    let mut total_height: u32 = 0;
+    let mut max_width = 0;
+    for decoder in &decoders {
+        let (width, height) = decoder.dimensions();
+        total_height = total_height
+            .checked_add(height)
+            .ok_or(ConcatError::DimensionOverflow)?;
+        max_width = max(max_width, width);
+    }
+
+    // Make an image buffer large enough to contain all images
+    let mut buffer: ImageBuffer<P, Vec<P::Subpixel>> = ImageBuffer::new(max_width, total_height);
+    let row_stride = buffer.sample_layout().height_stride * mem::size_of::<P::Subpixel>();
+    // Decoders write raw bytes, so work with a byte view of the buffer
+    let raw: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+    let total = decoders.len();
+
+    // The contiguous fast path only lines up when every image's rows are as wide as the buffer's
+    if decoders
+        .iter()
+        .all(|decoder| decoder.dimensions().0 == max_width)
+    {
+        // Loop through decoders, decoding directly into ImageBuffer
+        let mut byte_start: u64 = 0;
+        for (i, decoder) in decoders.into_iter().enumerate() {
+            let byte_len = decoder.total_bytes();
+            let byte_end = byte_start + byte_len;
+
+            // Target portion of buffer for n-th image
+            let slice = raw.get_mut(byte_start as usize..byte_end as usize).unwrap();
+
+            // Decode image into buffer slice, bailing on the first image that fails to decode
+            decoder.read_image(slice)?;
+            callback(i + 1, total);
+
+            byte_start = byte_end;
+        }
+    } else {
+        // Narrower images need their rows placed at the buffer's stride, so decode each into a
+        // temp buffer and copy it over one row at a time at x=0
+        let mut row_start = 0;
+        for (i, decoder) in decoders.into_iter().enumerate() {
+            let height = decoder.dimensions().1;
+            let mut decoded = vec![0; decoder.total_bytes() as usize];
+            decoder.read_image(&mut decoded)?;
+            callback(i + 1, total);
+
+            row_start = copy_rows_into(raw, row_stride, row_start, &decoded, height);
+        }
+    }
+
+    // Return concatenated images
+    Ok(buffer)
+}
+
+/// Loads given images on a rayon thread pool and vertically concatenates them.
+///
+/// Each image is decoded into its own buffer in parallel and then copied into the
+/// final buffer, trading the single copy that `load_and_vert_concat_images` avoids
+/// for parallel decoding. This tends to pay off when decoding dominates, e.g. many
+/// or large compressed images.
+///
+/// Requires the `rayon` feature.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `RgbImage`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_parallel;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_parallel(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn load_and_vert_concat_images_parallel(
+    image_paths: &[PathBuf],
+) -> Result<RgbImage, ConcatError> {
+    load_and_vert_concat_images_parallel_as(image_paths)
+}
+
+/// Loads given images on a rayon thread pool and vertically concatenates them into an ImageBuffer of pixel type `P`.
+///
+/// See `load_and_vert_concat_images_parallel` and `load_and_vert_concat_images_as`.
+///
+/// Requires the `rayon` feature.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+///
+/// # Returns
+/// * `ImageBuffer<P, Vec<P::Subpixel>>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_parallel_as;
+/// use std::path::PathBuf;
+/// let img_result = load_and_vert_concat_images_parallel_as::<image::Rgb<u8>>(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")]);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn load_and_vert_concat_images_parallel_as<P>(
+    image_paths: &[PathBuf],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+{
+    use rayon::prelude::*;
+
+    if image_paths.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    // Decode every image into its own buffer on the thread pool
+    let decoded = image_paths
+        .par_iter()
+        .map(|path| {
+            let decoder = open_decoder::<P>(path)?;
+            let dimensions = decoder.dimensions();
+            let mut decoded = vec![0; decoder.total_bytes() as usize];
+            decoder.read_image(&mut decoded)?;
+            Ok((dimensions, decoded))
+        })
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+
+    let total_height = decoded
+        .iter()
+        .try_fold(0u32, |total, ((_, height), _)| total.checked_add(*height))
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let max_width = decoded
+        .iter()
+        .map(|((width, _), _)| *width)
+        .max()
+        .unwrap_or(0);
+
+    // Make an image buffer large enough to contain all images
+    let mut buffer: ImageBuffer<P, Vec<P::Subpixel>> = ImageBuffer::new(max_width, total_height);
+    let row_stride = buffer.sample_layout().height_stride * mem::size_of::<P::Subpixel>();
+    let raw: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+    // Copy decoded images into the final buffer one after another
+    let mut row_start = 0;
+    for ((_, height), decoded) in &decoded {
+        row_start = copy_rows_into(raw, row_stride, row_start, decoded, *height);
+    }
+
+    Ok(buffer)
+}
+
+/// Loads given images on a rayon thread pool, decoding at most `max_in_flight` at once.
+///
+/// Like `load_and_vert_concat_images_parallel`, but each decoded image is copied into
+/// the final buffer and dropped as soon as it's done, with a semaphore holding back
+/// further decodes while `max_in_flight` are underway. Memory use is then the output
+/// plus `max_in_flight` source images, rather than the output plus every source image.
+/// Headers are read first to size the buffer, so each file is opened twice.
+///
+/// A `max_in_flight` of 0 is treated as 1. Requires the `rayon` feature.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `max_in_flight` - Most images decoded and held in memory at the same time
+///
+/// # Returns
+/// * `RgbImage`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_vert_concat_images_parallel_bounded;
+/// use std::path::PathBuf;
+/// let paths = [PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")];
+/// let img_result = load_and_vert_concat_images_parallel_bounded(&paths, 2);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn load_and_vert_concat_images_parallel_bounded(
+    image_paths: &[PathBuf],
+    max_in_flight: usize,
+) -> Result<RgbImage, ConcatError> {
+    vert_concat_parallel_bounded(image_paths, max_in_flight, |_| {})
+}
+
+/// Implements `load_and_vert_concat_images_parallel_bounded`, calling `on_decode`
+/// with the number of decoded buffers alive each time one is allocated
+#[cfg(feature = "rayon")]
+fn vert_concat_parallel_bounded<P>(
+    image_paths: &[PathBuf],
+    max_in_flight: usize,
+    on_decode: impl Fn(usize) + Sync,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: PixelWithColorType,
+    P::Subpixel: Pod,
+{
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    if image_paths.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    // Only headers are read here, checking every image before any decoding starts
+    let dimensions = image_paths
+        .par_iter()
+        .map(|path| Ok(open_decoder::<P>(path)?.dimensions()))
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+
+    let total_height = dimensions
+        .iter()
+        .try_fold(0u32, |total, (_, height)| total.checked_add(*height))
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let max_width = dimensions
+        .iter()
+        .map(|(width, _)| *width)
+        .max()
+        .unwrap_or(0);
+
+    let mut buffer: ImageBuffer<P, Vec<P::Subpixel>> = ImageBuffer::new(max_width, total_height);
+    let row_stride = buffer.sample_layout().height_stride * mem::size_of::<P::Subpixel>();
+    let raw: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+
+    // Split the buffer into the rows each image covers so they can be filled independently
+    let mut regions = Vec::with_capacity(dimensions.len());
+    let mut rest = raw;
+    for (_, height) in &dimensions {
+        let (region, tail) = rest.split_at_mut(*height as usize * row_stride);
+        regions.push(region);
+        rest = tail;
+    }
+
+    let semaphore = semaphore::Semaphore::new(max_in_flight.max(1));
+    let alive = AtomicUsize::new(0);
+    image_paths
+        .par_iter()
+        .zip(&dimensions)
+        .zip(regions)
+        .try_for_each(|((path, &expected), region)| -> Result<(), ConcatError> {
+            let _permit = semaphore.acquire();
+            let decoder = open_decoder::<P>(path)?;
+            if decoder.dimensions() != expected {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Image {} changed while it was being loaded", path.display()),
+                )
+                .into());
+            }
+
+            let mut decoded = vec![0; decoder.total_bytes() as usize];
+            on_decode(alive.fetch_add(1, Ordering::SeqCst) + 1);
+            let result = decoder.read_image(&mut decoded);
+            if result.is_ok() {
+                copy_rows_into(region, row_stride, 0, &decoded, expected.1);
+            }
+            // Free the decoded image before handing the permit to the next decode
+            drop(decoded);
+            alive.fetch_sub(1, Ordering::SeqCst);
+            Ok(result?)
+        })?;
+
+    Ok(buffer)
+}
+
+/// Reads an image's dimensions from its header without decoding any pixels
+///
+/// This is how the direct loaders size their buffer up front, and it's much
+/// cheaper than opening the image when planning a layout over many files.
+/// The format is guessed from the file's contents rather than its extension.
+///
+/// # Arguments
+/// * `path` - Path to the image
+///
+/// # Returns
+/// * `Result<(u32, u32), ConcatError>` - Width and height
+///
+/// # Example
+/// ```
+/// use image_concat_rs::image_dimensions_fast;
+/// use std::path::Path;
+/// let (width, height) = image_dimensions_fast(Path::new("./test/1.png")).unwrap();
+/// ```
+pub fn image_dimensions_fast(path: &Path) -> Result<(u32, u32), ConcatError> {
+    let img = ImageReader::open(path).map_err(|err| open_error(path, err))?;
+    Ok(img.with_guessed_format()?.into_dimensions()?)
+}
+
+/// Opens an image decoder, checking the image can be decoded straight into a buffer of `P`
+fn open_decoder<P: PixelWithColorType>(path: &Path) -> Result<impl ImageDecoder, ConcatError> {
+    check_color_type::<P, _>(open_reader(path)?.into_decoder()?, path)
+}
+
+/// Opens the image at `path` for decoding, erroring for plain CMYK JPEGs
+///
+/// The file is opened once, and the same reader is used for the CMYK check and
+/// then decoding. The format is guessed from the file's contents, falling back
+/// to its extension.
+pub(crate) fn open_reader(path: &Path) -> Result<ImageReader<BufReader<File>>, ConcatError> {
+    let file = File::open(path).map_err(|err| open_error(path, err))?;
+    let mut reader = BufReader::new(file);
+    check_cmyk_jpeg(&mut reader, &path.display().to_string())?;
+
+    let mut img = ImageReader::new(reader);
+    if let Ok(format) = ImageFormat::from_path(path) {
+        img.set_format(format);
+    }
+    Ok(img.with_guessed_format()?)
+}
+
+/// Opens and decodes the image at `path` with `open_reader`
+pub(crate) fn decode_path(path: &Path) -> Result<DynamicImage, ConcatError> {
+    Ok(open_reader(path)?.decode()?)
+}
+
+/// Opens an image decoder over a memory map of the file, see `open_decoder`
+#[cfg(feature = "mmap")]
+fn open_mmap_decoder<P: PixelWithColorType>(path: &Path) -> Result<impl ImageDecoder, ConcatError> {
+    let file = std::fs::File::open(path).map_err(|err| open_error(path, err))?;
+    // SAFETY: the map is only read while decoding. As with any memory map, the file being
+    // truncated or modified by another process during that time is undefined behaviour,
+    // which callers of the mmap loaders accept.
+    let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| open_error(path, err))?;
+
+    check_cmyk_jpeg_bytes(&map, &path.display().to_string())?;
+    let img = ImageReader::new(Cursor::new(map)).with_guessed_format()?;
+    check_color_type::<P, _>(img.into_decoder()?, path)
+}
+
+/// Adds the path to an error from opening an image
+fn open_error(path: &Path, err: std::io::Error) -> std::io::Error {
+    std::io::Error::new(
+        err.kind(),
+        format!("Error opening image {}: {}", path.display(), err),
+    )
+}
+
+/// Checks a decoder's color type matches `P` so it can be decoded straight into the buffer
+fn check_color_type<P: PixelWithColorType, D: ImageDecoder>(
+    decoder: D,
+    path: &Path,
+) -> Result<D, ConcatError> {
+    // Decoded bytes are written straight into the buffer so they must already be in its layout
+    let color_type = decoder.color_type();
+    if ExtendedColorType::from(color_type) != P::COLOR_TYPE {
+        return Err(image::ImageError::Parameter(ParameterError::from_kind(
+            ParameterErrorKind::Generic(format!(
+                "Image {} has color type {:?}, expected {:?}",
+                path.display(),
+                color_type,
+                P::COLOR_TYPE
+            )),
+        ))
+        .into());
+    }
+
+    Ok(decoder)
+}
+
+/// Copies the rows of a decoded image into a raw buffer starting at byte `row_start`,
+/// stepping by the buffer's `row_stride`. Returns the start of the row after the image.
+fn copy_rows_into(
+    raw: &mut [u8],
+    row_stride: usize,
+    mut row_start: usize,
+    decoded: &[u8],
+    height: u32,
+) -> usize {
+    if height == 0 {
+        return row_start;
+    }
+
+    let row_len = decoded.len() / height as usize;
+    for row in decoded.chunks_exact(row_len) {
+        raw[row_start..row_start + row_len].copy_from_slice(row);
+        row_start += row_stride;
+    }
+
+    row_start
+}
+
+/// Loads given images and concatenate them into columns.
+/// Images are directly decoded into vertical columns to avoid unnecessary copying,
+/// but horizontal concatenation of those columns requires copying of already decoded images.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `columns` - number of columns to split images into
+///
+/// # Returns
+/// * `RgbImage`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::load_and_column_concat_images;
+/// use std::path::PathBuf;
+/// let img_result = load_and_column_concat_images(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")], 2);
+/// ```
+pub fn load_and_column_concat_images(
+    image_paths: &[PathBuf],
+    columns: usize,
+) -> Result<RgbImage, ConcatError> {
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    // Vertical concatenation is more performant than horizontal because we can use the contiguous
+    // nature of the memory to directly decode images into a final buffer one after another without
+    // making copies of data. Horitontal concatenation would require decoding one row of each image
+    // into a final buffer before moving to the next line which I don't see a way to do in ImageDecoder.
+    // As such, we'll performantly vertical concatenate columns of images and then horizontally
+    // concatenate the columns into a single image buffer.
+    // Unfortunately, the horizontal concatenation will require explicitly copying memory over.
+
+    // vec to store our vertically concatenated columns
+    let mut col_buffs = Vec::new();
+
+    // Max number of images per column
+    let chunk_size = image_paths.len() / columns;
+    // Starting index of columns that will have less images
+    let chunk_remainder = image_paths.len() % columns;
+
+    // Build image columns
     let mut start = 0;
-    let mut x = 0;
     for idx in 0..columns {
         // Determine if this is a full size column or a partial column
         let chunk_size = if idx < chunk_remainder {
@@ -312,61 +985,7671 @@ pub fn column_concat_images<P: Pixel>(
         };
         let end = start + chunk_size;
 
-        // Add an empty image if more columns than images were requested
-        let col_blits = if start >= num_images {
-            vec![ImageBlit {
-                img: &blank_col,
-                x,
-                y: 0,
-            }]
-        } else {
-            // create a list of ImageBlits to draw a column of images
-            get_concat_blits(&images[start..end], ConcatDirection::Vertical, x, 0)
-        };
+        // Columns beyond the number of images are left out rather than loading nothing
+        if start == end {
+            break;
+        }
 
-        // determine x coord of next column by finding the widest blit
-        let max_width = col_blits
-            .iter()
-            .map(|blit| blit.x + blit.img.width())
-            .max()
-            .unwrap();
-        // account for current x coord so only current image width is considered
-        let max_width = max_width - x;
+        // Grab dynamic chunk size of images and concat verically
+        let buff = load_and_vert_concat_images(&image_paths[start..end])?;
+        col_buffs.push(buff);
 
-        // add blits to blit buffer
-        blits.extend(col_blits);
+        start = end;
+    }
 
-        // set next column starting x coord
-        x += max_width;
+    concat_images(&col_buffs, ConcatDirection::Horizontal)
+}
 
-        // update image index
-        start = end;
+/// Loads given images as thumbnails and concatenates them into a grid.
+/// Each image is shrunk as soon as it's decoded so only one full size image is held in memory at a time.
+///
+/// Images are downscaled with `imageops::thumbnail` so their longest side is
+/// `max_thumb`, preserving aspect ratio. Images already within `max_thumb` are
+/// left at their original size. Thumbnails are laid out like `grid_concat_images`.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `max_thumb` - Maximum width and height of each thumbnail
+/// * `columns` - Number of thumbnails per row
+///
+/// # Returns
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no paths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::thumbnail_concat_images;
+/// use std::path::PathBuf;
+/// let paths: Vec<_> = (1..=8).map(|i| PathBuf::from(format!("./test/{i}.png"))).collect();
+/// let img_result = thumbnail_concat_images(&paths, 128, 4);
+/// ```
+pub fn thumbnail_concat_images(
+    image_paths: &[PathBuf],
+    max_thumb: u32,
+    columns: usize,
+) -> Result<RgbImage, ConcatError> {
+    if image_paths.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
     }
 
-    // execute all blits
-    place_images_in_buffer(&blits)
+    let thumbs = image_paths
+        .iter()
+        .map(|path| {
+            let img = decode_path(path)?.into_rgb8();
+            let (width, height) = img.dimensions();
+            if width <= max_thumb && height <= max_thumb {
+                return Ok(img);
+            }
+
+            let (thumb_width, thumb_height) = if width >= height {
+                (max_thumb, scale_dimension(height, max_thumb, width))
+            } else {
+                (scale_dimension(width, max_thumb, height), max_thumb)
+            };
+            Ok(imageops::thumbnail(&img, thumb_width, thumb_height))
+        })
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+
+    grid_concat_images(&thumbs, columns)
 }
 
-mod tests {
-    #[test]
-    fn test_concat_images() {
-        let imgs = vec![
-            image::open("./test/1.png").unwrap().into_rgb8(),
-            image::open("./test/2.png").unwrap().into_rgb8(),
-        ];
-        let expected_w = imgs.iter().map(|img| img.width()).max().unwrap();
-        let expected_h: u32 = imgs.iter().map(|img| img.height()).sum();
+/// Decodes images from in-memory byte buffers and concatenates them.
+/// The format of each buffer is guessed from its contents and images are converted to RGB.
+///
+/// # Arguments
+/// * `buffers` - Slice of encoded image bytes, e.g. the contents of PNG files as `Vec<u8>`s or `&[u8]`s
+/// * `direction` - Direction to concatenate images in
+///
+/// # Returns
+/// * `RgbImage`
+/// * `ConcatError::Empty` if no buffers are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images_from_bytes, ConcatDirection};
+/// let buffers = vec![std::fs::read("./test/1.png").unwrap(), std::fs::read("./test/2.png").unwrap()];
+/// let img_result = concat_images_from_bytes(&buffers, ConcatDirection::Vertical);
+/// ```
+pub fn concat_images_from_bytes<B: AsRef<[u8]>>(
+    buffers: &[B],
+    direction: ConcatDirection,
+) -> Result<RgbImage, ConcatError> {
+    let imgs = buffers
+        .iter()
+        .enumerate()
+        .map(|(idx, buffer)| {
+            check_cmyk_jpeg_bytes(buffer.as_ref(), &format!("buffer {idx}"))?;
+            let img = ImageReader::new(Cursor::new(buffer.as_ref()))
+                .with_guessed_format()?
+                .decode()?;
+            Ok(img.into_rgb8())
+        })
+        .collect::<Result<Vec<_>, ConcatError>>()?;
 
-        let img_result = super::concat_images(&imgs, super::ConcatDirection::Vertical).unwrap();
-        // TODO maybe check against gold images
-        assert_eq!(img_result.width(), expected_w);
-        assert_eq!(img_result.height(), expected_h);
-    }
+    concat_images(&imgs, direction)
+}
 
-    #[test]
-    fn test_column_concat_images_unbalanced() {
-        let single_img = vec![image::open("./test/1.png").unwrap().into_rgb8()];
-        // request concatting 2 columns, but only pass 1 image
-        let _img_result = super::column_concat_images(&single_img, 2).unwrap();
+/// Concatenates encoded images entirely in memory, returning the result as PNG bytes.
+///
+/// Nothing touches the filesystem, so this works on targets without one such as
+/// `wasm32-unknown-unknown` in the browser, where inputs arrive as byte arrays
+/// from `fetch` or a file input and the output is handed back to JS as is.
+///
+/// # Arguments
+/// * `buffers` - Slice of encoded image bytes, e.g. the contents of PNG files
+/// * `direction` - Direction to concatenate images in
+///
+/// # Returns
+/// * `Vec<u8>` - The concatenated image encoded as a PNG
+/// * `ConcatError::Empty` if no buffers are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_bytes_to_png, ConcatDirection};
+/// let img1 = std::fs::read("./test/1.png").unwrap();
+/// let img2 = std::fs::read("./test/2.png").unwrap();
+/// let png_result = concat_bytes_to_png(&[&img1, &img2], ConcatDirection::Vertical);
+/// ```
+pub fn concat_bytes_to_png(
+    buffers: &[&[u8]],
+    direction: ConcatDirection,
+) -> Result<Vec<u8>, ConcatError> {
+    let img = concat_images_from_bytes(buffers, direction)?;
+
+    let mut png = Cursor::new(Vec::new());
+    write_concat(&img, &mut png, ImageFormat::Png)?;
+    Ok(png.into_inner())
+}
+
+/// Encodes a concatenated image to a writer rather than a file
+///
+/// Lets the output go straight into an in-memory buffer, a temp file or an
+/// HTTP response body without a round trip through the filesystem. Some
+/// encoders seek back to fill in headers, so the writer must be seekable,
+/// e.g. wrap a `Vec<u8>` in a `Cursor`.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to encode
+/// * `writer` - Destination for the encoded bytes
+/// * `format` - Format to encode as, which must support the image's pixel type
+///
+/// # Returns
+/// * `Result<(), ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images, write_concat, ConcatDirection};
+/// use image::ImageFormat;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img = concat_images(&[img1,img2], ConcatDirection::Vertical).unwrap();
+/// let mut jpeg = std::io::Cursor::new(Vec::new());
+/// write_concat(&img, &mut jpeg, ImageFormat::Jpeg).unwrap();
+/// ```
+pub fn write_concat<P, W>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    writer: &mut W,
+    format: ImageFormat,
+) -> Result<(), ConcatError>
+where
+    P: PixelWithColorType,
+    [P::Subpixel]: EncodableLayout,
+    W: Write + Seek,
+{
+    img.write_to(writer, format)?;
+    Ok(())
+}
+
+/// Concatenates images and encodes the result as the best quality JPEG that fits in `max_bytes`
+///
+/// For outputs with a size budget, such as email attachments. Qualities from 1 to
+/// 100 are binary searched, encoding once per step, so this costs around seven
+/// encodes. Size very nearly always shrinks with quality, so the result is the
+/// highest quality under the budget or close to it.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `max_bytes` - Largest encoded size allowed
+///
+/// # Returns
+/// * `Result<Vec<u8>, ConcatError>` - The encoded JPEG
+/// * `ConcatError::ExceedsMaxBytes` if even quality 1 doesn't fit
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_and_encode_under, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let jpeg_result = concat_and_encode_under(&[img1, img2], ConcatDirection::Vertical, 20_000);
+/// ```
+pub fn concat_and_encode_under(
+    images: &[RgbImage],
+    direction: ConcatDirection,
+    max_bytes: usize,
+) -> Result<Vec<u8>, ConcatError> {
+    let img = concat_images(images, direction)?;
+    let encode = |quality: u8| -> Result<Vec<u8>, ConcatError> {
+        let mut jpeg = Vec::new();
+        img.write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, quality))?;
+        Ok(jpeg)
+    };
+
+    let (mut low, mut high) = (1u8, 100u8);
+    let mut best = None;
+    let mut smallest = 0;
+    while low <= high {
+        let quality = low + (high - low) / 2;
+        let jpeg = encode(quality)?;
+        if jpeg.len() <= max_bytes {
+            best = Some(jpeg);
+            low = quality + 1;
+        } else {
+            smallest = jpeg.len();
+            if quality == 1 {
+                break;
+            }
+            high = quality - 1;
+        }
+    }
+
+    best.ok_or(ConcatError::ExceedsMaxBytes {
+        bytes: smallest,
+        max_bytes,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConcatDirection {
+    Vertical,
+    Horizontal,
+}
+
+/// Placement of an image along the cross axis when it is smaller than the
+/// largest image, i.e. horizontal placement in a vertical concat and vertical
+/// placement in a horizontal concat.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// Left or top edge
+    #[default]
+    Start,
+    /// Centered, rounding towards the start when the space can't be evenly split
+    Center,
+    /// Right or bottom edge
+    End,
+}
+
+impl Alignment {
+    /// Offset of an item of `size` placed within `extent`
+    fn offset(self, extent: u32, size: u32) -> u32 {
+        let free_space = extent.saturating_sub(size);
+        match self {
+            Alignment::Start => 0,
+            Alignment::Center => free_space / 2,
+            Alignment::End => free_space,
+        }
+    }
+}
+
+/// Vertical placement of an image within a grid row taller than it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RowAlign {
+    /// Top of the row
+    #[default]
+    Top,
+    /// Centered, rounding towards the top when the space can't be evenly split
+    Middle,
+    /// Bottom of the row
+    Bottom,
+}
+
+impl From<RowAlign> for Alignment {
+    fn from(row_align: RowAlign) -> Self {
+        match row_align {
+            RowAlign::Top => Alignment::Start,
+            RowAlign::Middle => Alignment::Center,
+            RowAlign::Bottom => Alignment::End,
+        }
+    }
+}
+
+/// Order images are laid out in along the concatenation direction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Order {
+    /// Left to right or top to bottom
+    #[default]
+    Forward,
+    /// Right to left or bottom to top, so the first image ends up on the right or bottom
+    Reverse,
+}
+
+/// Gap inserted between adjacent images
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Spacing {
+    /// Gap of a fixed number of pixels
+    Fixed(u32),
+    /// Gap as a percentage of the average image height (vertical) or width
+    /// (horizontal), so montages keep their proportions at any source resolution
+    Percent(f32),
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Spacing::Fixed(0)
+    }
+}
+
+impl From<u32> for Spacing {
+    fn from(pixels: u32) -> Self {
+        Spacing::Fixed(pixels)
+    }
+}
+
+impl Spacing {
+    /// Gap in pixels between `images` concatenated in `direction`, rounded to the nearest pixel
+    fn pixels<P: Pixel>(
+        self,
+        images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+        direction: ConcatDirection,
+    ) -> u32 {
+        match self {
+            Spacing::Fixed(pixels) => pixels,
+            Spacing::Percent(_) if images.is_empty() => 0,
+            Spacing::Percent(percent) => {
+                let total: u64 = images
+                    .iter()
+                    .map(|img| match direction {
+                        ConcatDirection::Vertical => img.height() as u64,
+                        ConcatDirection::Horizontal => img.width() as u64,
+                    })
+                    .sum();
+                let average = total as f64 / images.len() as f64;
+                // Saturates, so negative percentages give no gap
+                (average * percent as f64 / 100.0).round() as u32
+            }
+        }
+    }
+}
+
+/// Options controlling how images are concatenated
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{Alignment, ConcatDirection, ConcatOptions, Spacing};
+/// let options = ConcatOptions {
+///     spacing: Spacing::Fixed(8),
+///     alignment: Alignment::Center,
+///     background: Some(image::Rgb([255, 255, 255])),
+///     ..ConcatOptions::new(ConcatDirection::Vertical)
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConcatOptions<P: Pixel> {
+    /// Direction images are concatenated in
+    pub direction: ConcatDirection,
+    /// Placement of images smaller than the largest image on the cross axis
+    pub alignment: Alignment,
+    /// Gap inserted between adjacent images
+    pub spacing: Spacing,
+    /// Whether the first image is placed at the start or end of the concatenation
+    pub order: Order,
+    /// Color of any gaps or padding, zero filled (black/transparent) if `None`
+    pub background: Option<P>,
+    /// Line drawn over each seam between adjacent images, `width` pixels thick.
+    /// Unlike spacing it doesn't add any space, it overwrites the pixels at the
+    /// seam, or the middle of the gap when there's spacing.
+    pub divider: Option<Border<P>>,
+    /// Largest output, in pixels, that may be allocated. Larger concatenations
+    /// return `ConcatError::ExceedsMaxPixels` before any memory is allocated.
+    pub max_pixels: Option<u64>,
+    /// Color the whole output is shifted toward once everything else is drawn, see `apply_tint`
+    pub tint: Option<Tint<P>>,
+    /// Radius in pixels of rounded corners cut into each image, 0 for square
+    /// corners. The cut away corners show the background, or are zero filled
+    /// (transparent for pixel types with alpha) if there isn't one.
+    pub corner_radius: u32,
+    /// Soft shadow drawn behind each image. The output grows to fit shadows cast
+    /// past its edges, but shadows falling on a neighbouring image are hidden
+    /// beneath it, so leave room with `spacing` for them to show.
+    pub shadow: Option<Shadow<P>>,
+}
+
+impl<P: Pixel> ConcatOptions<P> {
+    /// Options for a plain concatenation in `direction`, with every other option off
+    ///
+    /// # Defaults
+    /// * `alignment` - `Alignment::Start`
+    /// * `spacing` - `Spacing::Fixed(0)`
+    /// * `order` - `Order::Forward`
+    /// * `background` - `None`, zero filled padding
+    /// * `divider` - `None`
+    /// * `max_pixels` - `None`, no size limit
+    /// * `tint` - `None`
+    /// * `corner_radius` - 0, square corners
+    /// * `shadow` - `None`
+    pub fn new(direction: ConcatDirection) -> Self {
+        ConcatOptions {
+            direction,
+            alignment: Alignment::Start,
+            spacing: Spacing::Fixed(0),
+            order: Order::Forward,
+            background: None,
+            divider: None,
+            max_pixels: None,
+            tint: None,
+            corner_radius: 0,
+            shadow: None,
+        }
+    }
+}
+
+/// Concatenates ImageBuffers vertically or horizontally
+///
+/// A single image is returned as a straight clone of its buffer, skipping
+/// blit layout and placement entirely.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::Empty` if no images are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = concat_images(&[img1,img2], ConcatDirection::Vertical);
+/// ```
+pub fn concat_images<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    direction: ConcatDirection,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    // Nothing to lay out for one image, so copy its buffer in one go
+    if let [img] = images {
+        return Ok(img.clone());
+    }
+
+    let blits = get_concat_blits(images, direction, 0, 0);
+    place_images_in_buffer(&blits)
+}
+
+/// Concatenates ImageBuffers vertically or horizontally, requiring them to line up exactly
+///
+/// Same as `concat_images`, but instead of padding images that are narrower
+/// (vertical) or shorter (horizontal) than the others, an error is returned for
+/// the first image whose cross axis size differs from the first image's.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::DimensionMismatch` if the images don't share a cross axis size
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images_strict, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = concat_images_strict(&[img1,img2], ConcatDirection::Vertical);
+/// ```
+pub fn concat_images_strict<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    direction: ConcatDirection,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let cross_axis = |img: &ImageBuffer<P, Vec<P::Subpixel>>| match direction {
+        ConcatDirection::Vertical => img.width(),
+        ConcatDirection::Horizontal => img.height(),
+    };
+
+    if let Some(first) = images.first() {
+        let expected = cross_axis(first);
+        if let Some((index, got)) = images
+            .iter()
+            .map(cross_axis)
+            .enumerate()
+            .find(|(_, got)| *got != expected)
+        {
+            return Err(ConcatError::DimensionMismatch {
+                index,
+                expected,
+                got,
+            });
+        }
+    }
+
+    concat_images(images, direction)
+}
+
+/// Creates a solid color image to use as a spacer in a layout
+///
+/// Spacers can be mixed in with real images, e.g. to leave a gap between two
+/// groups in `concat_images` or to reserve an empty cell in a grid.
+///
+/// # Arguments
+/// * `width` - Width of the spacer
+/// * `height` - Height of the spacer
+/// * `color` - Pixel every position of the spacer is filled with
+///
+/// # Returns
+/// * `ImageBuffer<P, Vec<P::Subpixel>>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images, spacer, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let gap = spacer(img1.width(), 20, image::Rgb([255, 255, 255]));
+/// let img_result = concat_images(&[img1, gap, img2], ConcatDirection::Vertical);
+/// ```
+pub fn spacer<P: Pixel>(width: u32, height: u32, color: P) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    ImageBuffer::from_pixel(width, height, color)
+}
+
+/// Region of a buffer an image was placed in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// Whether the point (`x`, `y`) falls within the rect, e.g. for mapping a click back to an image
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
+    }
+}
+
+/// A concatenated image and the region each source image was placed in
+pub type ImageWithLayout<P> = (ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>, Vec<Rect>);
+
+/// Concatenates ImageBuffers vertically or horizontally, returning where each image was placed
+///
+/// Same as `concat_images`, but also returns the region of the output each
+/// input landed in, in the same order as `images`, so callers can map points in
+/// the output back to a source image.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<(ImageBuffer, Vec<Rect>), ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images_with_layout, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let (img, layout) = concat_images_with_layout(&[img1,img2], ConcatDirection::Vertical).unwrap();
+/// let clicked = layout.iter().position(|rect| rect.contains(10, img.height() - 1));
+/// assert_eq!(clicked, Some(1));
+/// ```
+pub fn concat_images_with_layout<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    direction: ConcatDirection,
+) -> Result<ImageWithLayout<P>, ConcatError> {
+    let blits = get_concat_blits(images, direction, 0, 0);
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(&blits)?;
+
+    let layout = blits
+        .iter()
+        .zip(&top_lefts)
+        .map(|(blit, (x, y))| {
+            let (width, height) = blit.dimensions();
+            Rect {
+                x: *x,
+                y: *y,
+                width,
+                height,
+            }
+        })
+        .collect();
+
+    let buffer = copy_blits_into(
+        ImageBuffer::new(total_width, total_height),
+        &blits,
+        &top_lefts,
+    )?;
+    Ok((buffer, layout))
+}
+
+/// Where one image of a LayoutPlan will be drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlannedImage {
+    /// Index of the image in the slice that was planned
+    pub index: usize,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Size of a concatenation and the placement of each image, computed without drawing anything
+///
+/// With the `serde` feature enabled this can be serialized, e.g. to send to a UI
+/// for previewing a layout.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutPlan {
+    pub width: u32,
+    pub height: u32,
+    /// Placement of every image, in the same order as the planned slice
+    pub images: Vec<PlannedImage>,
+}
+
+/// Plans a concatenation without allocating or drawing the output
+///
+/// Returns exactly where `concat_images_with_options` would place each image so
+/// a layout can be inspected or previewed before committing to the pixel copy.
+/// Dividers and backgrounds don't move any images so they don't change the plan,
+/// while shadows pad the output and shift images in to make room.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to plan
+/// * `options` - Direction, alignment, spacing and order of the concatenation
+///
+/// # Returns
+/// * `Result<LayoutPlan, ConcatError>`
+/// * `ConcatError::Empty` if no images are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{plan_concat, ConcatDirection, ConcatOptions};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let plan = plan_concat(&[img1,img2], &ConcatOptions::new(ConcatDirection::Vertical)).unwrap();
+/// for planned in &plan.images {
+///     println!("image {} at {},{}", planned.index, planned.x, planned.y);
+/// }
+/// ```
+pub fn plan_concat<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    options: &ConcatOptions<P>,
+) -> Result<LayoutPlan, ConcatError> {
+    let (pad_left, pad_top, pad_right, pad_bottom) = shadow_padding(options);
+    let blits = get_concat_blits_with_options(images, options, pad_left, pad_top);
+    let Placements {
+        dimensions: (width, height),
+        top_lefts,
+    } = resolve_placements(&blits)?;
+    let width = width
+        .checked_add(pad_right)
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let height = height
+        .checked_add(pad_bottom)
+        .ok_or(ConcatError::DimensionOverflow)?;
+
+    let images = blits
+        .iter()
+        .zip(top_lefts)
+        .enumerate()
+        .map(|(index, (blit, (x, y)))| {
+            let (width, height) = blit.dimensions();
+            PlannedImage {
+                index,
+                x,
+                y,
+                width,
+                height,
+            }
+        })
+        .collect();
+
+    Ok(LayoutPlan {
+        width,
+        height,
+        images,
+    })
+}
+
+/// Concatenates ImageBuffers vertically or horizontally over a background color
+///
+/// When images differ in width (vertical) or height (horizontal) the leftover
+/// space is filled with `background` instead of being zero filled.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `background` - Pixel used to fill any padding regions
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images_with_background, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = concat_images_with_background(&[img1,img2], ConcatDirection::Vertical, image::Rgb([255, 255, 255]));
+/// ```
+pub fn concat_images_with_background<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    direction: ConcatDirection,
+    background: P,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let blits = get_concat_blits(images, direction, 0, 0);
+    place_images_in_buffer_with_background(&blits, background)
+}
+
+/// Concatenates DynamicImages of any pixel type into the narrowest type that fits them all
+///
+/// Rather than forcing every image into one pixel type up front, the output is
+/// promoted to the widest format needed: color if any image has color, alpha if
+/// any image has alpha, and the largest channel depth of any image. For example,
+/// an RGB8 and a LumaA8 image concatenate into RGBA8, and an RGB8 and an RGB16
+/// into RGB16. Grayscale float images are promoted to RGB float since there is
+/// no grayscale float DynamicImage.
+///
+/// # Arguments
+/// * `images` - Slice of DynamicImages to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<DynamicImage, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_dynamic_images, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap();
+/// let img2 = image::DynamicImage::ImageRgba8(image::open("./test/2.png").unwrap().into_rgba8());
+/// // output will be RGBA since img2 has alpha
+/// let img_result = concat_dynamic_images(&[img1, img2], ConcatDirection::Vertical);
+/// ```
+pub fn concat_dynamic_images(
+    images: &[DynamicImage],
+    direction: ConcatDirection,
+) -> Result<DynamicImage, ConcatError> {
+    let target = widest_format(images.iter().map(DynamicImage::color));
+    concat_heterogeneous(images, direction, target)
+}
+
+/// Narrowest PixelFormat that can hold every one of `colors` without losing color, alpha or depth
+fn widest_format(colors: impl Iterator<Item = ColorType>) -> PixelFormat {
+    let (mut color, mut alpha, mut channel_bytes) = (false, false, 1);
+    for color_type in colors {
+        color |= color_type.has_color();
+        alpha |= color_type.has_alpha();
+        channel_bytes = max(
+            channel_bytes,
+            color_type.bytes_per_pixel() / color_type.channel_count(),
+        );
+    }
+
+    match (channel_bytes, color, alpha) {
+        (1, false, false) => PixelFormat::Luma8,
+        (1, false, true) => PixelFormat::LumaA8,
+        (1, true, false) => PixelFormat::Rgb8,
+        (1, true, true) => PixelFormat::Rgba8,
+        (2, false, false) => PixelFormat::Luma16,
+        (2, false, true) => PixelFormat::LumaA16,
+        (2, true, false) => PixelFormat::Rgb16,
+        (2, true, true) => PixelFormat::Rgba16,
+        (_, _, false) => PixelFormat::Rgb32F,
+        (_, _, true) => PixelFormat::Rgba32F,
+    }
+}
+
+/// Pixel type a DynamicImage can be converted to, one per DynamicImage variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    Luma8,
+    LumaA8,
+    Rgb8,
+    Rgba8,
+    Luma16,
+    LumaA16,
+    Rgb16,
+    Rgba16,
+    Rgb32F,
+    Rgba32F,
+}
+
+impl PixelFormat {
+    /// ColorType of images in this format
+    fn color_type(self) -> ColorType {
+        match self {
+            PixelFormat::Luma8 => ColorType::L8,
+            PixelFormat::LumaA8 => ColorType::La8,
+            PixelFormat::Rgb8 => ColorType::Rgb8,
+            PixelFormat::Rgba8 => ColorType::Rgba8,
+            PixelFormat::Luma16 => ColorType::L16,
+            PixelFormat::LumaA16 => ColorType::La16,
+            PixelFormat::Rgb16 => ColorType::Rgb16,
+            PixelFormat::Rgba16 => ColorType::Rgba16,
+            PixelFormat::Rgb32F => ColorType::Rgb32F,
+            PixelFormat::Rgba32F => ColorType::Rgba32F,
+        }
+    }
+}
+
+/// Converts DynamicImages of any pixel type to `target` and concatenates them
+///
+/// Unlike `concat_dynamic_images`, which picks the output type from the inputs,
+/// the caller chooses it, e.g. `PixelFormat::Rgb8` to drop the alpha of some
+/// PNGs so they can be concatenated with JPEGs into an RGB image.
+///
+/// # Arguments
+/// * `images` - Slice of DynamicImages to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `target` - Pixel type every image is converted to
+///
+/// # Returns
+/// * `Result<DynamicImage, ConcatError>` - DynamicImage of the `target` variant
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_heterogeneous, ConcatDirection, PixelFormat};
+/// let img1 = image::open("./test/1.png").unwrap();
+/// let img2 = image::DynamicImage::ImageRgba8(image::open("./test/2.png").unwrap().into_rgba8());
+/// let img_result = concat_heterogeneous(&[img1, img2], ConcatDirection::Vertical, PixelFormat::Rgb8);
+/// ```
+pub fn concat_heterogeneous(
+    images: &[DynamicImage],
+    direction: ConcatDirection,
+    target: PixelFormat,
+) -> Result<DynamicImage, ConcatError> {
+    let img = match target {
+        PixelFormat::Luma8 => concat_converted(images, direction, DynamicImage::to_luma8)?.into(),
+        PixelFormat::LumaA8 => {
+            concat_converted(images, direction, DynamicImage::to_luma_alpha8)?.into()
+        }
+        PixelFormat::Rgb8 => concat_converted(images, direction, DynamicImage::to_rgb8)?.into(),
+        PixelFormat::Rgba8 => concat_converted(images, direction, DynamicImage::to_rgba8)?.into(),
+        PixelFormat::Luma16 => concat_converted(images, direction, DynamicImage::to_luma16)?.into(),
+        PixelFormat::LumaA16 => {
+            concat_converted(images, direction, DynamicImage::to_luma_alpha16)?.into()
+        }
+        PixelFormat::Rgb16 => concat_converted(images, direction, DynamicImage::to_rgb16)?.into(),
+        PixelFormat::Rgba16 => concat_converted(images, direction, DynamicImage::to_rgba16)?.into(),
+        PixelFormat::Rgb32F => concat_converted(images, direction, DynamicImage::to_rgb32f)?.into(),
+        PixelFormat::Rgba32F => {
+            concat_converted(images, direction, DynamicImage::to_rgba32f)?.into()
+        }
+    };
+
+    Ok(img)
+}
+
+/// Converts every image with `convert` and concatenates the results
+fn concat_converted<P: Pixel>(
+    images: &[DynamicImage],
+    direction: ConcatDirection,
+    convert: fn(&DynamicImage) -> ImageBuffer<P, Vec<P::Subpixel>>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let converted: Vec<_> = images.iter().map(convert).collect();
+    concat_images(&converted, direction)
+}
+
+/// Concatenates DynamicImages into raw RGBA8 bytes, e.g. for uploading as a GPU texture
+///
+/// Every image is converted to RGBA8 and the result is returned as tightly
+/// packed rows of `width * 4` bytes with no padding, top row first, which is
+/// the layout `wgpu` and OpenGL expect for an `Rgba8Unorm`/`GL_RGBA` texture.
+///
+/// # Arguments
+/// * `images` - Slice of DynamicImages to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<(bytes, width, height), ConcatError>`
+/// * `ConcatError::Empty` if no images are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_to_raw_rgba, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap();
+/// let img2 = image::open("./test/2.png").unwrap();
+/// let (bytes, width, height) = concat_to_raw_rgba(&[img1, img2], ConcatDirection::Vertical).unwrap();
+/// assert_eq!(bytes.len(), (width * height * 4) as usize);
+/// ```
+pub fn concat_to_raw_rgba(
+    images: &[DynamicImage],
+    direction: ConcatDirection,
+) -> Result<(Vec<u8>, u32, u32), ConcatError> {
+    let img = concat_converted(images, direction, DynamicImage::to_rgba8)?;
+    let (width, height) = img.dimensions();
+    Ok((img.into_raw(), width, height))
+}
+
+/// Flattens an RGBA image onto a gray checkerboard for previewing transparency
+///
+/// Transparent regions show the checkerboard, like an image editor would, and
+/// semi-transparent pixels are blended with it. Opaque pixels are unchanged.
+///
+/// # Arguments
+/// * `img` - RgbaImage to flatten
+/// * `cell` - Size in pixels of each checkerboard square, 0 is treated as 1
+///
+/// # Returns
+/// * `RgbImage`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images, flatten_over_checkerboard, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgba8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgba8();
+/// let img = concat_images(&[img1, img2], ConcatDirection::Vertical).unwrap();
+/// let preview = flatten_over_checkerboard(&img, 8);
+/// ```
+pub fn flatten_over_checkerboard(img: &RgbaImage, cell: u32) -> RgbImage {
+    const LIGHT: u16 = 204;
+    const DARK: u16 = 153;
+    let cell = cell.max(1);
+
+    RgbImage::from_fn(img.width(), img.height(), |x, y| {
+        let background = if (x / cell + y / cell).is_multiple_of(2) {
+            LIGHT
+        } else {
+            DARK
+        };
+        let [r, g, b, a] = img.get_pixel(x, y).0;
+        let a = a as u16;
+        // Rounded `src * a + background * (1 - a)` in 0..=255 fixed point
+        let blend = |c: u8| ((c as u16 * a + background * (255 - a) + 127) / 255) as u8;
+        Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+/// Scales ImageBuffers to a common cross axis size and then concatenates them
+///
+/// Every image is resized so its width (vertical) or height (horizontal) equals
+/// `target_cross_axis`, preserving its aspect ratio, which avoids ragged edges
+/// when concatenating images of differing sizes. This requires a resized copy of
+/// every image. Images with a zero width or height have no aspect ratio to keep
+/// and nothing to draw, so they're skipped.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `target_cross_axis` - Width (vertical) or height (horizontal) every image is scaled to
+/// * `filter` - Sampling filter used when resizing
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::Empty` if no images with a nonzero size are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images_scaled, ConcatDirection};
+/// use image::imageops::FilterType;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = concat_images_scaled(&[img1,img2], ConcatDirection::Vertical, 200, FilterType::Triangle);
+/// ```
+pub fn concat_images_scaled<P: Pixel + 'static>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    direction: ConcatDirection,
+    target_cross_axis: u32,
+    filter: FilterType,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let scaled: Vec<_> = images
+        .iter()
+        .filter(|img| img.width() > 0 && img.height() > 0)
+        .map(|img| {
+            let (width, height) = match direction {
+                ConcatDirection::Vertical => (
+                    target_cross_axis,
+                    scale_dimension(img.height(), target_cross_axis, img.width()),
+                ),
+                ConcatDirection::Horizontal => (
+                    scale_dimension(img.width(), target_cross_axis, img.height()),
+                    target_cross_axis,
+                ),
+            };
+            imageops::resize(img, width, height, filter)
+        })
+        .collect();
+
+    concat_images(&scaled, direction)
+}
+
+/// Side of an image that `normalize_to_smallest` matches up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    Width,
+    Height,
+}
+
+/// Downscales images so they all share the smallest image's width or height
+///
+/// Each image is shrunk, preserving its aspect ratio, until its `axis` matches
+/// the smallest size of that axis among `images`, so nothing is ever upscaled
+/// and blurred. Matching widths before a vertical concat, or heights before a
+/// horizontal one, gives it clean edges. Images already at the smallest size
+/// are copied as is rather than resampled.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to normalize
+/// * `axis` - Axis to match, `Axis::Width` or `Axis::Height`
+/// * `filter` - Sampling filter used when resizing
+///
+/// # Returns
+/// * `Vec<ImageBuffer>` - The images in the same order, empty if `images` is
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images, normalize_to_smallest, Axis, ConcatDirection};
+/// use image::imageops::FilterType;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::imageops::thumbnail(&img1, 211, 16);
+/// let imgs = normalize_to_smallest(&[img1, img2], Axis::Width, FilterType::Triangle);
+/// let img_result = concat_images(&imgs, ConcatDirection::Vertical);
+/// ```
+pub fn normalize_to_smallest<P: Pixel + 'static>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    axis: Axis,
+    filter: FilterType,
+) -> Vec<ImageBuffer<P, Vec<P::Subpixel>>> {
+    let size_of = |img: &ImageBuffer<P, Vec<P::Subpixel>>| match axis {
+        Axis::Width => img.width(),
+        Axis::Height => img.height(),
+    };
+    let Some(target) = images.iter().map(size_of).min() else {
+        return Vec::new();
+    };
+
+    images
+        .iter()
+        .map(|img| {
+            if size_of(img) == target {
+                return img.clone();
+            }
+            let (width, height) = match axis {
+                Axis::Width => (target, scale_dimension(img.height(), target, img.width())),
+                Axis::Height => (scale_dimension(img.width(), target, img.height()), target),
+            };
+            imageops::resize(img, width, height, filter)
+        })
+        .collect()
+}
+
+/// Scales `size` by `target / reference`, rounding to the nearest pixel and never going below 1
+///
+/// `reference` must be nonzero, otherwise the scale is infinite and saturates to `u32::MAX`.
+fn scale_dimension(size: u32, target: u32, reference: u32) -> u32 {
+    let scaled = (size as f64 * target as f64 / reference as f64).round() as u32;
+    max(scaled, 1)
+}
+
+/// Concatenates ImageBuffers according to a set of ConcatOptions
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `options` - How images are laid out and drawn, see `ConcatOptions`
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::ExceedsMaxPixels` if the output would be larger than `options.max_pixels`
+/// * `ConcatError::DimensionOverflow` if the output, including any room added for shadows, doesn't fit in a u32
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images_with_options, Border, ConcatDirection, ConcatOptions, Spacing};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let options = ConcatOptions {
+///     spacing: Spacing::Fixed(10),
+///     ..ConcatOptions::new(ConcatDirection::Vertical)
+/// };
+/// let img_result = concat_images_with_options(&[img1.clone(),img2.clone()], &options);
+/// // or a thin line marking where the images meet
+/// let options = ConcatOptions {
+///     divider: Some(Border { width: 1, color: image::Rgb([255, 0, 0]) }),
+///     ..ConcatOptions::new(ConcatDirection::Vertical)
+/// };
+/// let img_result = concat_images_with_options(&[img1,img2], &options);
+/// ```
+pub fn concat_images_with_options<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    options: &ConcatOptions<P>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let (pad_left, pad_top, pad_right, pad_bottom) = shadow_padding(options);
+    let blits = get_concat_blits_with_options(images, options, pad_left, pad_top);
+    if let Some(max_pixels) = options.max_pixels {
+        let (width, height) = blits_dimensions(&blits)?;
+        let pixels = (width as u64 + pad_right as u64) * (height as u64 + pad_bottom as u64);
+        if pixels > max_pixels {
+            return Err(ConcatError::ExceedsMaxPixels { pixels, max_pixels });
+        }
+    }
+
+    let mut buffer = match (options.shadow, options.background) {
+        (Some(shadow), background) => {
+            place_images_with_shadows(&blits, background, shadow, (pad_right, pad_bottom))?
+        }
+        (None, Some(background)) => place_images_in_buffer_with_background(&blits, background)?,
+        (None, None) => place_images_in_buffer(&blits)?,
+    };
+
+    if options.corner_radius > 0 {
+        for blit in &blits {
+            let (width, height) = blit.dimensions();
+            // Fade the corners into whatever the padding is filled with
+            let corner = |pixel: &mut P, coverage: f32| match options.background {
+                Some(background) => {
+                    *pixel = background.map2(pixel, |b, c| lerp(b, c, coverage));
+                }
+                None => {
+                    let zero = <P::Subpixel as image::Primitive>::DEFAULT_MIN_VALUE;
+                    pixel.apply(|c| lerp(zero, c, coverage))
+                }
+            };
+            let radius = options.corner_radius;
+            mask_corners(
+                &mut buffer,
+                (blit.x, blit.y),
+                (width, height),
+                radius,
+                corner,
+            );
+        }
+    }
+    if let Some(divider) = options.divider {
+        draw_dividers(&mut buffer, &blits, options.direction, divider);
+    }
+    if let Some(tint) = options.tint {
+        apply_tint(&mut buffer, tint.color, tint.strength);
+    }
+
+    Ok(buffer)
+}
+
+/// Pixels a shadow extends past the left, top, right and bottom of its image
+fn shadow_padding<P: Pixel>(options: &ConcatOptions<P>) -> (u32, u32, u32, u32) {
+    let Some(shadow) = options.shadow else {
+        return (0, 0, 0, 0);
+    };
+    let blur = shadow.blur as i64;
+    let pad = |extent: i64| extent.clamp(0, u32::MAX as i64) as u32;
+    (
+        pad(blur - shadow.offset_x as i64),
+        pad(blur - shadow.offset_y as i64),
+        pad(blur + shadow.offset_x as i64),
+        pad(blur + shadow.offset_y as i64),
+    )
+}
+
+/// Places blits over their shadows in a buffer padded by `extra` on the right and bottom
+fn place_images_with_shadows<P: Pixel>(
+    images: &[ImageBlit<P>],
+    background: Option<P>,
+    shadow: Shadow<P>,
+    (extra_width, extra_height): (u32, u32),
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let Placements {
+        dimensions: (width, height),
+        top_lefts,
+    } = resolve_placements(images)?;
+    let width = width
+        .checked_add(extra_width)
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let height = height
+        .checked_add(extra_height)
+        .ok_or(ConcatError::DimensionOverflow)?;
+
+    let mut buffer = match background {
+        Some(background) => ImageBuffer::from_pixel(width, height, background),
+        None => ImageBuffer::new(width, height),
+    };
+    // Every shadow goes down before any image so no shadow is drawn over an image
+    for (blit, &top_left) in images.iter().zip(&top_lefts) {
+        draw_shadow(&mut buffer, top_left, blit.dimensions(), shadow);
+    }
+
+    copy_blits_into(buffer, images, &top_lefts)
+}
+
+/// Blends the box blurred shadow of an image at `(x, y)` of the given size into `buffer`
+fn draw_shadow<P: Pixel>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    (x, y): (u32, u32),
+    (width, height): (u32, u32),
+    shadow: Shadow<P>,
+) {
+    let blur = shadow.blur as i64;
+    let opacity = shadow.opacity.clamp(0.0, 1.0);
+    // A box blurred rectangle is the product of a box blurred step along each axis, i.e. the
+    // fraction of the 2 * blur + 1 wide window around a pixel that overlaps the shadow
+    let coverage = |pixel: i64, start: i64, len: u32| {
+        let overlap = (pixel + blur + 1).min(start + len as i64) - (pixel - blur).max(start);
+        overlap.max(0) as f32 / (2 * blur + 1) as f32
+    };
+
+    let start_x = x as i64 + shadow.offset_x as i64;
+    let start_y = y as i64 + shadow.offset_y as i64;
+    let xs = (start_x - blur).max(0)..(start_x + width as i64 + blur).min(buffer.width() as i64);
+    let ys = (start_y - blur).max(0)..(start_y + height as i64 + blur).min(buffer.height() as i64);
+    for py in ys {
+        let coverage_y = coverage(py, start_y, height);
+        for px in xs.clone() {
+            let strength = coverage(px, start_x, width) * coverage_y * opacity;
+            let pixel = buffer.get_pixel_mut(px as u32, py as u32);
+            *pixel = pixel.map2(&shadow.color, |c, s| lerp(c, s, strength));
+        }
+    }
+}
+
+/// Draws `divider` across the whole cross axis centered on each seam between consecutive blits
+fn draw_dividers<P: Pixel>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    blits: &[ImageBlit<P>],
+    direction: ConcatDirection,
+    divider: Border<P>,
+) {
+    // Main axis span of every blit, sorted since reversed plans run backwards
+    let mut spans: Vec<(u32, u32)> = blits
+        .iter()
+        .map(|blit| {
+            let (width, height) = blit.dimensions();
+            match direction {
+                ConcatDirection::Vertical => (blit.y, blit.y + height),
+                ConcatDirection::Horizontal => (blit.x, blit.x + width),
+            }
+        })
+        .collect();
+    spans.sort_unstable();
+
+    let (main_extent, cross_extent) = match direction {
+        ConcatDirection::Vertical => (buffer.height(), buffer.width()),
+        ConcatDirection::Horizontal => (buffer.width(), buffer.height()),
+    };
+
+    for pair in spans.windows(2) {
+        let seam = pair[0].1 + (pair[1].0 - pair[0].1) / 2;
+        let start = seam.saturating_sub(divider.width / 2);
+        let thickness = divider.width.min(main_extent - start);
+        match direction {
+            ConcatDirection::Vertical => {
+                fill_rect(buffer, 0, start, cross_extent, thickness, divider.color)
+            }
+            ConcatDirection::Horizontal => {
+                fill_rect(buffer, start, 0, thickness, cross_extent, divider.color)
+            }
+        }
+    }
+}
+
+/// Point on an image that an ImageBlit's coordinate refers to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Offset from the top left of an image of the given size to the anchor point
+    fn offset(self, width: u32, height: u32) -> (u32, u32) {
+        let x = match self {
+            Anchor::TopLeft | Anchor::CenterLeft | Anchor::BottomLeft => 0,
+            Anchor::TopCenter | Anchor::Center | Anchor::BottomCenter => width / 2,
+            Anchor::TopRight | Anchor::CenterRight | Anchor::BottomRight => width,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::TopCenter | Anchor::TopRight => 0,
+            Anchor::CenterLeft | Anchor::Center | Anchor::CenterRight => height / 2,
+            Anchor::BottomLeft | Anchor::BottomCenter | Anchor::BottomRight => height,
+        };
+        (x, y)
+    }
+}
+
+/// Rotation or flip applied to an image as it is placed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transform {
+    #[default]
+    None,
+    /// Rotate 90 degrees clockwise
+    Rotate90,
+    Rotate180,
+    /// Rotate 270 degrees clockwise
+    Rotate270,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+}
+
+impl Transform {
+    /// Size of an image of `width` x `height` once transformed
+    fn dimensions(self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            Transform::Rotate90 | Transform::Rotate270 => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    /// Copy of `img` with the transform applied
+    fn apply<P: Pixel>(
+        self,
+        img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    ) -> ImageBuffer<P, Vec<P::Subpixel>> {
+        let (width, height) = img.dimensions();
+        let (out_width, out_height) = self.dimensions(width, height);
+
+        // Map each output pixel back to the source pixel it comes from
+        ImageBuffer::from_fn(out_width, out_height, |x, y| {
+            let (src_x, src_y) = match self {
+                Transform::None => (x, y),
+                Transform::Rotate90 => (y, height - 1 - x),
+                Transform::Rotate180 => (width - 1 - x, height - 1 - y),
+                Transform::Rotate270 => (width - 1 - y, x),
+                Transform::FlipHorizontal => (width - 1 - x, y),
+                Transform::FlipVertical => (x, height - 1 - y),
+            };
+            *img.get_pixel(src_x, src_y)
+        })
+    }
+}
+
+/// Placement of an image at a target coordinate
+///
+/// By default `x` and `y` are where the top left of the image will be placed,
+/// `origin` can be changed to place the image by another point such as its center.
+/// The image can also be rotated or flipped as it's placed with `transform`, in
+/// which case `origin` and the buffer size use the transformed dimensions.
+/// Setting `src_rect` places only that region of the image, as if it had been
+/// cropped beforehand. `opacity` fades the image over what's beneath it, which
+/// only `place_images_in_buffer_blended` honours, the copying placements ignore it.
+#[derive(Clone, Copy)]
+pub struct ImageBlit<'a, P: Pixel> {
+    pub img: &'a ImageBuffer<P, Vec<P::Subpixel>>,
+    pub x: u32,
+    pub y: u32,
+    /// Point on the image that `x` and `y` refer to
+    pub origin: Anchor,
+    /// Rotation or flip applied to the image when it is placed
+    pub transform: Transform,
+    /// Region of `img` to place instead of all of it, clamped to the image's bounds
+    pub src_rect: Option<Rect>,
+    /// How strongly the image covers what's beneath it when blended, from 0.0 to 1.0
+    pub opacity: f32,
+}
+
+impl<'a, P: Pixel> ImageBlit<'a, P> {
+    /// Places the top left of `img` at (`x`, `y`)
+    pub fn new(img: &'a ImageBuffer<P, Vec<P::Subpixel>>, x: u32, y: u32) -> Self {
+        ImageBlit {
+            img,
+            x,
+            y,
+            origin: Anchor::TopLeft,
+            transform: Transform::None,
+            src_rect: None,
+            opacity: 1.0,
+        }
+    }
+
+    /// Places the `origin` point of the image at (`x`, `y`) instead of the top left
+    pub fn with_origin(mut self, origin: Anchor) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Rotates or flips the image as it's placed, see `Transform`
+    ///
+    /// Placing a transformed blit makes a temporary transformed copy of the image.
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Places only the `src_rect` region of the image, e.g. one tile of a sprite sheet
+    ///
+    /// Plain `place_images_in_buffer` copies straight from the region, so nothing is
+    /// cropped into a temporary image first. The rect is clamped to the image's bounds.
+    pub fn with_src_rect(mut self, src_rect: Rect) -> Self {
+        self.src_rect = Some(src_rect);
+        self
+    }
+
+    /// Blends the image at `opacity` over what's beneath it, e.g. 0.5 for a ghosted overlay
+    ///
+    /// Only used by `place_images_in_buffer_blended`.
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Size the image will occupy once placed, accounting for `src_rect` and `transform`
+    pub fn dimensions(&self) -> (u32, u32) {
+        let src = self.source();
+        self.transform.dimensions(src.width, src.height)
+    }
+
+    /// Region of `img` that is placed, the whole image unless `src_rect` is set
+    fn source(&self) -> Rect {
+        let (width, height) = self.img.dimensions();
+        match self.src_rect {
+            None => Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            },
+            Some(rect) => {
+                let x = rect.x.min(width);
+                let y = rect.y.min(height);
+                Rect {
+                    x,
+                    y,
+                    width: rect.width.min(width - x),
+                    height: rect.height.min(height - y),
+                }
+            }
+        }
+    }
+
+    /// Image to copy into the buffer, borrowed unless it has to be cropped or transformed first
+    fn image(&self) -> Cow<'a, ImageBuffer<P, Vec<P::Subpixel>>> {
+        let img = match self.src_rect {
+            None => Cow::Borrowed(self.img),
+            Some(_) => {
+                let src = self.source();
+                let mut cropped = ImageBuffer::new(src.width, src.height);
+                copy_image_into(&mut cropped, self.img, src, 0, 0);
+                Cow::Owned(cropped)
+            }
+        };
+        match self.transform {
+            Transform::None => img,
+            transform => Cow::Owned(transform.apply(&img)),
+        }
+    }
+
+    /// Top left coord the image would be placed at, which may be negative for anchored blits
+    fn top_left(&self) -> (i64, i64) {
+        let (width, height) = self.dimensions();
+        let (offset_x, offset_y) = self.origin.offset(width, height);
+        (
+            self.x as i64 - offset_x as i64,
+            self.y as i64 - offset_y as i64,
+        )
+    }
+}
+
+/// Places ImageBuffers into a single buffer
+///   
+/// The list of images and placements will be scanned to determine the total size
+/// of the buffer then all images will be copied into the buffer. Anything not
+/// covered by an image is left zero filled, which is black for RGB and fully
+/// transparent for RGBA.
+///
+/// The goal of this function is to enable direction agnostic concatenation with
+/// as few copies as possible. For example, instead of doing column concatenation
+/// by creating a column of images and then horizontally concatenating them,
+/// which would require an unnecessary copy of the columns into the final
+/// horizontal alignment, this takes all the desired placements and copies them
+/// into a single buffer.
+///
+/// A single blit works too, giving a buffer sized to that image plus its
+/// offset from the origin, which is handy for cropping or transforming one image.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all images
+/// * `ConcatError::Empty` if no blits are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = place_images_in_buffer(&[ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, img1.width(), 0)]);
+/// ```
+pub fn place_images_in_buffer<P: Pixel>(
+    images: &[ImageBlit<P>],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    // Create an image buffer large enough to contain all images
+    let buffer = ImageBuffer::new(total_width, total_height);
+
+    copy_blits_into(buffer, images, &top_lefts)
+}
+
+/// Places ImageBuffers into a caller owned buffer, reusing its allocation
+///
+/// Same as `place_images_in_buffer`, but instead of allocating a new buffer
+/// every call, `buffer` is resized to fit the blits and cleared to zero before
+/// the images are copied in. Its memory is only reallocated when it's too small,
+/// so repeatedly placing equally sized plans, e.g. montages of video frames,
+/// allocates once.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+/// * `buffer` - ImageBuffer to place the images into, any previous contents are cleared
+///
+/// # Returns
+/// * `Result<(), ConcatError>`
+/// * `ConcatError::Empty` if no blits are given, leaving `buffer` untouched
+/// * `ConcatError::DimensionOverflow` if the output is too large to address, also leaving `buffer` untouched
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_concat_blits, place_images_in_buffer_into, ConcatDirection};
+/// let mut buffer = image::RgbImage::new(0, 0);
+/// for _frame in 0..3 {
+///     let frames = [
+///         image::open("./test/1.png").unwrap().into_rgb8(),
+///         image::open("./test/2.png").unwrap().into_rgb8(),
+///     ];
+///     let blits = get_concat_blits(&frames, ConcatDirection::Horizontal, 0, 0);
+///     place_images_in_buffer_into(&blits, &mut buffer).unwrap();
+/// }
+/// ```
+pub fn place_images_in_buffer_into<P: Pixel>(
+    images: &[ImageBlit<P>],
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+) -> Result<(), ConcatError> {
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    // Reuse the existing allocation, which only grows if it has to
+    let len = (total_width as usize)
+        .checked_mul(total_height as usize)
+        .and_then(|pixels| pixels.checked_mul(P::CHANNEL_COUNT as usize))
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let mut raw = mem::replace(buffer, ImageBuffer::new(0, 0)).into_raw();
+    raw.clear();
+    raw.resize(len, <P::Subpixel as image::Primitive>::DEFAULT_MIN_VALUE);
+    let resized = ImageBuffer::from_raw(total_width, total_height, raw)
+        .ok_or(ConcatError::DimensionOverflow)?;
+
+    *buffer = copy_blits_into(resized, images, &top_lefts)?;
+    Ok(())
+}
+
+/// Places ImageBuffers into a single buffer, copying on a rayon thread pool
+///
+/// Produces the same buffer as `place_images_in_buffer`, but splits the buffer
+/// into rows that are filled in parallel, each thread copying the slice of every
+/// image that crosses its row. Blits may overlap, later blits still overwrite
+/// earlier ones. Any transforms are applied up front before copying starts.
+///
+/// Requires the `rayon` feature.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all images
+/// * `ConcatError::Empty` if no blits are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_parallel,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = place_images_in_buffer_parallel(&[ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, img1.width(), 0)]);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn place_images_in_buffer_parallel<P>(
+    images: &[ImageBlit<P>],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError>
+where
+    P: Pixel + Sync,
+    P::Subpixel: Send + Sync,
+{
+    use rayon::prelude::*;
+
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    // Transform images before splitting up the work so threads only read them
+    let sources: Vec<_> = images.iter().map(ImageBlit::image).collect();
+    let channels = P::CHANNEL_COUNT as usize;
+    let row_len = total_width as usize * channels;
+
+    let mut buffer: ImageBuffer<P, Vec<P::Subpixel>> = ImageBuffer::new(total_width, total_height);
+    if row_len == 0 {
+        return Ok(buffer);
+    }
+
+    // Rows are disjoint, so each can be written by a different thread
+    buffer
+        .par_chunks_mut(row_len)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let y = y as u32;
+            for (img, &(x, top)) in sources.iter().zip(&top_lefts) {
+                if y < top || y - top >= img.height() {
+                    continue;
+                }
+                let src_len = img.width() as usize * channels;
+                let src_start = (y - top) as usize * src_len;
+                let dst_start = x as usize * channels;
+                row[dst_start..dst_start + src_len]
+                    .copy_from_slice(&img.as_raw()[src_start..src_start + src_len]);
+            }
+        });
+
+    Ok(buffer)
+}
+
+/// Places ImageBuffers into a single buffer pre-filled with a background color
+///
+/// Same as `place_images_in_buffer`, but any region of the buffer not covered
+/// by an image will be set to `background` instead of being zero filled.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+/// * `background` - Pixel used to fill the buffer before images are placed
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all images
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_with_background,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let blits = [ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, 10, img1.height())];
+/// let img_result = place_images_in_buffer_with_background(&blits, image::Rgb([255, 255, 255]));
+/// ```
+pub fn place_images_in_buffer_with_background<P: Pixel>(
+    images: &[ImageBlit<P>],
+    background: P,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    // Create an image buffer large enough to contain all images, filled with the background
+    let buffer = ImageBuffer::from_pixel(total_width, total_height, background);
+
+    copy_blits_into(buffer, images, &top_lefts)
+}
+
+/// Places ImageBuffers into a single buffer pre-filled with a tiled pattern
+///
+/// Same as `place_images_in_buffer_with_background`, but instead of a solid
+/// color the buffer is filled by repeating `pattern` from the top left corner,
+/// e.g. a checkerboard to preview transparency. An empty pattern leaves the
+/// buffer zero filled.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+/// * `pattern` - ImageBuffer tiled across the buffer before images are placed
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all images
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_with_pattern,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let checkerboard = image::RgbImage::from_fn(16, 16, |x, y| {
+///     if (x / 8 + y / 8) % 2 == 0 { image::Rgb([255, 255, 255]) } else { image::Rgb([200, 200, 200]) }
+/// });
+/// let blits = [ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, 10, img1.height())];
+/// let img_result = place_images_in_buffer_with_pattern(&blits, &checkerboard);
+/// ```
+pub fn place_images_in_buffer_with_pattern<P: Pixel>(
+    images: &[ImageBlit<P>],
+    pattern: &ImageBuffer<P, Vec<P::Subpixel>>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    // Create an image buffer large enough to contain all images, tiled with the pattern
+    let buffer = if pattern.width() == 0 || pattern.height() == 0 {
+        ImageBuffer::new(total_width, total_height)
+    } else {
+        ImageBuffer::from_fn(total_width, total_height, |x, y| {
+            *pattern.get_pixel(x % pattern.width(), y % pattern.height())
+        })
+    };
+
+    copy_blits_into(buffer, images, &top_lefts)
+}
+
+/// Places ImageBuffers into a single buffer, alpha blending overlapping images
+///
+/// Same as `place_images_in_buffer`, but instead of overwriting pixels each
+/// image is composited over whatever the earlier blits left beneath it with
+/// `imageops::overlay`, so semi-transparent images blend correctly. A blit's
+/// `opacity` scales how much of it shows through on top of that, for pixel types
+/// with or without alpha. The buffer starts out zero filled, i.e. fully
+/// transparent for pixel types with alpha.
+///
+/// Faded blits are blended with straight alpha, which darkens them where they
+/// cover transparent parts of the buffer. See `place_images_in_buffer_blended_with_alpha`
+/// to blend them premultiplied instead.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin, drawn back to front
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all images
+/// * `ConcatError::Empty` if no blits are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_blended,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgba8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgba8();
+/// // img2 overlaps the bottom right corner of img1
+/// let blits = [ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, img1.width() / 2, img1.height() / 2)];
+/// let img_result = place_images_in_buffer_blended(&blits);
+/// ```
+pub fn place_images_in_buffer_blended<P: Pixel>(
+    images: &[ImageBlit<P>],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    place_images_in_buffer_blended_with_alpha(images, AlphaMode::Straight)
+}
+
+/// How `place_images_in_buffer_blended_with_alpha` treats color under partial alpha
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlphaMode {
+    /// Colors are blended as stored, the way `imageops::overlay` does. Fading a
+    /// blit by `opacity` mixes in the zeroed color of transparent pixels beneath
+    /// it, leaving a dark fringe wherever it doesn't cover opaque pixels.
+    #[default]
+    Straight,
+    /// Colors are weighted by their alpha before blending and divided back out
+    /// after, so transparent pixels contribute nothing and edges stay clean.
+    Premultiplied,
+}
+
+/// Places ImageBuffers into a single buffer, alpha blending them in the given `AlphaMode`
+///
+/// Same as `place_images_in_buffer_blended`, which uses `AlphaMode::Straight`.
+/// With `AlphaMode::Premultiplied` every blit, faded or not, is composited with
+/// the source over operator on premultiplied colors, which avoids dark halos
+/// around semi-transparent images placed over transparent regions. Pixel types
+/// without alpha blend the same either way.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin, drawn back to front
+/// * `mode` - Whether colors are blended straight or premultiplied
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all images
+/// * `ConcatError::Empty` if no blits are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_blended_with_alpha, AlphaMode, ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgba8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgba8();
+/// let blits = [ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, 30, 10).with_opacity(0.5)];
+/// let img_result = place_images_in_buffer_blended_with_alpha(&blits, AlphaMode::Premultiplied);
+/// ```
+pub fn place_images_in_buffer_blended_with_alpha<P: Pixel>(
+    images: &[ImageBlit<P>],
+    mode: AlphaMode,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let Placements {
+        dimensions: (total_width, total_height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    let premultiplied = mode == AlphaMode::Premultiplied && P::HAS_ALPHA;
+    let mut buffer: ImageBuffer<P, Vec<P::Subpixel>> = ImageBuffer::new(total_width, total_height);
+    for (blit, (x, y)) in images.iter().zip(top_lefts) {
+        let img = blit.image();
+        let opacity = blit.opacity.clamp(0.0, 1.0);
+        if premultiplied {
+            for (img_x, img_y, pixel) in img.enumerate_pixels() {
+                blend_premultiplied(buffer.get_pixel_mut(x + img_x, y + img_y), pixel, opacity);
+            }
+            continue;
+        }
+        if opacity >= 1.0 {
+            imageops::overlay(&mut buffer, &*img, x as i64, y as i64);
+            continue;
+        }
+
+        // Composite as usual, then fade from what was beneath towards the result
+        for (img_x, img_y, pixel) in img.enumerate_pixels() {
+            let below = buffer.get_pixel_mut(x + img_x, y + img_y);
+            let mut over = *below;
+            over.blend(pixel);
+            *below = below.map2(&over, |below, over| lerp(below, over, opacity));
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Composites `over` faded by `opacity` onto `below` with premultiplied color, alpha being the last channel
+fn blend_premultiplied<P: Pixel>(below: &mut P, over: &P, opacity: f32) {
+    let max_value = <P::Subpixel as image::Primitive>::DEFAULT_MAX_VALUE
+        .to_f32()
+        .unwrap_or(1.0);
+    // Integer channels are rounded to the nearest value, float channels are kept as is
+    let round = |value: f32| {
+        if max_value > 1.0 {
+            value.round()
+        } else {
+            value
+        }
+    };
+    let unit = |value: P::Subpixel| value.to_f32().unwrap_or_default() / max_value;
+
+    let alpha_index = P::CHANNEL_COUNT as usize - 1;
+    let over = over.channels();
+    let below = below.channels_mut();
+    let over_alpha = unit(over[alpha_index]) * opacity;
+    let below_alpha = unit(below[alpha_index]);
+    let alpha = over_alpha + below_alpha * (1.0 - over_alpha);
+
+    for (below, over) in below[..alpha_index].iter_mut().zip(&over[..alpha_index]) {
+        let color = unit(*over) * over_alpha + unit(*below) * below_alpha * (1.0 - over_alpha);
+        let color = if alpha > 0.0 { color / alpha } else { 0.0 };
+        *below = num_traits::cast(round(color * max_value)).unwrap_or(*below);
+    }
+    below[alpha_index] = num_traits::cast(round(alpha * max_value)).unwrap_or(below[alpha_index]);
+}
+
+/// Places ImageBuffers into a buffer of a fixed size, clipping anything out of bounds
+///
+/// Unlike `place_images_in_buffer`, the buffer isn't sized to fit the blits. Any
+/// part of an image that falls outside of `width` x `height`, including past the
+/// left or top edge from an anchored blit, is dropped instead of causing an error.
+/// Images that are entirely out of bounds are skipped.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+/// * `width` - Width of the buffer
+/// * `height` - Height of the buffer
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing the visible portion of all images
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_clipped,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// // only the left half of img2 will fit
+/// let blits = [ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, img1.width() / 2, img1.height())];
+/// let img_result = place_images_in_buffer_clipped(&blits, img1.width(), img1.height() * 2);
+/// ```
+pub fn place_images_in_buffer_clipped<P: Pixel>(
+    images: &[ImageBlit<P>],
+    width: u32,
+    height: u32,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    clip_blits_into(ImageBuffer::new(width, height), images)
+}
+
+/// Places ImageBuffers onto a canvas of a fixed size filled with a background color
+///
+/// Same as `place_images_in_buffer_clipped`, but any part of the canvas not
+/// covered by an image is set to `background`. Useful for targeting a fixed
+/// output size such as a poster, e.g. by centering content with `Anchor::Center`
+/// at the middle of the canvas.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+/// * `width` - Width of the canvas
+/// * `height` - Height of the canvas
+/// * `background` - Pixel used to fill the canvas before images are placed
+///
+/// # Returns
+/// * `ImageBuffer` - `width` x `height` ImageBuffer containing the visible portion of all images
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_sized,Anchor,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// // center the image on a 1920x1080 canvas
+/// let blits = [ImageBlit::new(&img1, 960, 540).with_origin(Anchor::Center)];
+/// let img_result = place_images_in_buffer_sized(&blits, 1920, 1080, image::Rgb([255, 255, 255]));
+/// ```
+pub fn place_images_in_buffer_sized<P: Pixel>(
+    images: &[ImageBlit<P>],
+    width: u32,
+    height: u32,
+    background: P,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    clip_blits_into(ImageBuffer::from_pixel(width, height, background), images)
+}
+
+/// Centers a single image on a canvas of a fixed size filled with a background color
+///
+/// Handy for normalizing thumbnails of differing sizes to a square before
+/// gridding them. An image larger than the canvas is cropped evenly on each side.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to center
+/// * `canvas_width` - Width of the canvas
+/// * `canvas_height` - Height of the canvas
+/// * `background` - Pixel used to fill the canvas around the image
+///
+/// # Returns
+/// * `ImageBuffer` - `canvas_width` x `canvas_height` ImageBuffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::center_in_canvas;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let size = img1.width().max(img1.height());
+/// let square = center_in_canvas(&img1, size, size, image::Rgb([0, 0, 0]));
+/// ```
+pub fn center_in_canvas<P: Pixel>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    canvas_width: u32,
+    canvas_height: u32,
+    background: P,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    let mut canvas = ImageBuffer::from_pixel(canvas_width, canvas_height, background);
+    let x = (canvas_width as i64 - img.width() as i64) / 2;
+    let y = (canvas_height as i64 - img.height() as i64) / 2;
+    // replace clips to the canvas, so oversized images can't fail
+    imageops::replace(&mut canvas, img, x, y);
+    canvas
+}
+
+/// Copies the visible portion of every blit into `buffer`
+fn clip_blits_into<P: Pixel>(
+    mut buffer: ImageBuffer<P, Vec<P::Subpixel>>,
+    images: &[ImageBlit<P>],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    for blit in images {
+        let (x, y) = blit.top_left();
+        copy_clipped(&mut buffer, &blit.image(), x, y)?;
+    }
+
+    Ok(buffer)
+}
+
+/// Copies the portion of `img` placed with its top left at (`x`, `y`) that overlaps `buffer`
+fn copy_clipped<P: Pixel>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    x: i64,
+    y: i64,
+) -> Result<(), ConcatError> {
+    // Visible span of the image in buffer coords
+    let left = x.max(0);
+    let top = y.max(0);
+    let right = (x + img.width() as i64).min(buffer.width() as i64);
+    let bottom = (y + img.height() as i64).min(buffer.height() as i64);
+    if right <= left || bottom <= top {
+        return Ok(());
+    }
+
+    let visible = img.view(
+        (left - x) as u32,
+        (top - y) as u32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    );
+    buffer.copy_from(&*visible, left as u32, top as u32)?;
+    Ok(())
+}
+
+/// Color wash applied over a finished image, see `apply_tint`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tint<P: Pixel> {
+    pub color: P,
+    /// How far each pixel moves toward `color`, from 0.0 (unchanged) to 1.0 (solid `color`)
+    pub strength: f32,
+}
+
+/// Shifts every pixel of an image toward a tint color, e.g. to fade out a
+/// concatenation that sits behind a watermark or overlay
+///
+/// Each channel moves linearly toward the matching channel of `tint` by
+/// `strength`. Alpha is left alone, so transparent regions stay transparent.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to tint in place
+/// * `tint` - Color pixels are shifted toward, its alpha is ignored
+/// * `strength` - 0.0 leaves the image unchanged and 1.0 fills it with `tint`, values outside are clamped
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{apply_tint, concat_images, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let mut img = concat_images(&[img1,img2], ConcatDirection::Vertical).unwrap();
+/// apply_tint(&mut img, image::Rgb([255, 255, 255]), 0.5);
+/// ```
+pub fn apply_tint<P: Pixel>(img: &mut ImageBuffer<P, Vec<P::Subpixel>>, tint: P, strength: f32) {
+    let strength = strength.clamp(0.0, 1.0);
+    // Alpha is always the last channel of the image crate's pixel types
+    let color_channels = P::CHANNEL_COUNT as usize - usize::from(P::HAS_ALPHA);
+    for pixel in img.pixels_mut() {
+        let channels = pixel.channels_mut()[..color_channels].iter_mut();
+        for (channel, &target) in channels.zip(tint.channels()) {
+            *channel = lerp(*channel, target, strength);
+        }
+    }
+}
+
+/// Corner of an image something is pinned to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Alpha blends a watermark, such as a logo, into a corner of an image
+///
+/// The watermark sits `margin` pixels in from both edges of the chosen corner
+/// and any part that doesn't fit on `base` is clipped. Its alpha is scaled by
+/// `opacity` before blending, so transparent parts of the watermark stay
+/// transparent at any opacity.
+///
+/// # Arguments
+/// * `base` - RgbaImage to draw the watermark onto
+/// * `watermark` - RgbaImage to draw
+/// * `position` - Corner of `base` the watermark is placed in
+/// * `margin` - Gap in pixels between the watermark and the edges of `base`
+/// * `opacity` - From 0.0 (invisible) to 1.0 (the watermark's own alpha), values outside are clamped
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_images, overlay_watermark, ConcatDirection, Corner};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgba8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgba8();
+/// let logo = image::open("./test/3.png").unwrap().thumbnail(32, 32).into_rgba8();
+/// let mut img = concat_images(&[img1,img2], ConcatDirection::Horizontal).unwrap();
+/// overlay_watermark(&mut img, &logo, Corner::BottomRight, 8, 0.5);
+/// ```
+pub fn overlay_watermark(
+    base: &mut RgbaImage,
+    watermark: &RgbaImage,
+    position: Corner,
+    margin: u32,
+    opacity: f32,
+) {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let mut faded = watermark.clone();
+    for pixel in faded.pixels_mut() {
+        pixel[3] = (pixel[3] as f32 * opacity).round() as u8;
+    }
+
+    // Signed so a watermark larger than the base is clipped rather than wrapping around
+    let margin = margin as i64;
+    let left = margin;
+    let right = base.width() as i64 - watermark.width() as i64 - margin;
+    let top = margin;
+    let bottom = base.height() as i64 - watermark.height() as i64 - margin;
+    let (x, y) = match position {
+        Corner::TopLeft => (left, top),
+        Corner::TopRight => (right, top),
+        Corner::BottomLeft => (left, bottom),
+        Corner::BottomRight => (right, bottom),
+    };
+    imageops::overlay(base, &faded, x, y);
+}
+
+/// Rounds the corners of an image by fading them out to transparent
+///
+/// Pixels outside a quarter circle of `radius` in each corner become fully
+/// transparent and pixels on its edge are partially transparent so the curve
+/// is antialiased. The radius is limited to half the shorter side, which gives
+/// a circle or pill shape.
+///
+/// # Arguments
+/// * `img` - RgbaImage to round in place
+/// * `radius` - Corner radius in pixels, 0 leaves the image unchanged
+///
+/// # Example
+/// ```
+/// use image_concat_rs::round_corners;
+/// let mut img = image::open("./test/1.png").unwrap().into_rgba8();
+/// round_corners(&mut img, 16);
+/// ```
+pub fn round_corners(img: &mut RgbaImage, radius: u32) {
+    let dimensions = img.dimensions();
+    mask_corners(img, (0, 0), dimensions, radius, |pixel, coverage| {
+        pixel[3] = (pixel[3] as f32 * coverage).round() as u8;
+    });
+}
+
+/// Calls `mask` with the coverage of every pixel of the `size` region at
+/// `origin` that's cut into by its rounded corners, from 0.0 (fully outside
+/// the curve) to just under 1.0
+fn mask_corners<P: Pixel>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    (x0, y0): (u32, u32),
+    (width, height): (u32, u32),
+    radius: u32,
+    mut mask: impl FnMut(&mut P, f32),
+) {
+    let radius = radius.min(width / 2).min(height / 2);
+    let r = radius as f32;
+    for y in 0..radius {
+        for x in 0..radius {
+            // Distance from the pixel center to the center of the corner's circle
+            let (dx, dy) = (r - x as f32 - 0.5, r - y as f32 - 0.5);
+            let coverage = (r - (dx * dx + dy * dy).sqrt() + 0.5).clamp(0.0, 1.0);
+            if coverage >= 1.0 {
+                continue;
+            }
+            // The same offset mirrored into each corner
+            let corners = [
+                (x, y),
+                (width - 1 - x, y),
+                (x, height - 1 - y),
+                (width - 1 - x, height - 1 - y),
+            ];
+            for (cx, cy) in corners {
+                if let Some(pixel) = buffer.get_pixel_mut_checked(x0 + cx, y0 + cy) {
+                    mask(pixel, coverage);
+                }
+            }
+        }
+    }
+}
+
+/// Gaussian blurs an image, e.g. to soften a patterned background
+///
+/// A thin wrapper over `imageops::blur` that leaves the image untouched for a
+/// `sigma` of 0 or less rather than erroring. `box_blur` is a faster but
+/// blockier alternative for large radii.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to blur
+/// * `sigma` - Standard deviation of the gaussian in pixels
+///
+/// # Returns
+/// * `ImageBuffer` - Blurred copy of `img`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::blur;
+/// let img = image::open("./test/1.png").unwrap().into_rgb8();
+/// let blurred = blur(&img, 4.0);
+/// ```
+pub fn blur<P: Pixel + 'static>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    sigma: f32,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    if sigma <= 0.0 {
+        return img.clone();
+    }
+    imageops::blur(img, sigma)
+}
+
+/// Box blurs an image, averaging each pixel with its neighbours within `radius`
+///
+/// Runs a horizontal and then a vertical pass with a running sum, so the cost
+/// doesn't depend on the radius. Pixels past the image's edges are treated as
+/// copies of the edge pixels.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to blur
+/// * `radius` - Pixels on each side of a pixel that are averaged with it, 0 leaves the image unchanged
+///
+/// # Returns
+/// * `ImageBuffer` - Blurred copy of `img`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::box_blur;
+/// let img = image::open("./test/1.png").unwrap().into_rgb8();
+/// let blurred = box_blur(&img, 8);
+/// ```
+pub fn box_blur<P: Pixel>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    radius: u32,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    if radius == 0 || img.width() == 0 || img.height() == 0 {
+        return img.clone();
+    }
+    let horizontal = box_blur_pass(img, radius, true);
+    box_blur_pass(&horizontal, radius, false)
+}
+
+/// One box blur pass along rows if `horizontal`, otherwise along columns
+fn box_blur_pass<P: Pixel>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    radius: u32,
+    horizontal: bool,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    let (width, height) = img.dimensions();
+    let (lines, len) = if horizontal {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    let at = |line: u32, i: i64| {
+        let i = i.clamp(0, len as i64 - 1) as u32;
+        if horizontal {
+            img.get_pixel(i, line)
+        } else {
+            img.get_pixel(line, i)
+        }
+    };
+    let radius = radius as i64;
+    let window = (2 * radius + 1) as f64;
+    // Integer channels are rounded to the nearest value, float channels are kept as is
+    let max_value = <P::Subpixel as image::Primitive>::DEFAULT_MAX_VALUE;
+    let integral = max_value.to_f64().unwrap_or(1.0) > 1.0;
+
+    let mut out: ImageBuffer<P, Vec<P::Subpixel>> = ImageBuffer::new(width, height);
+    let mut sums = vec![0f64; P::CHANNEL_COUNT as usize];
+    for line in 0..lines {
+        sums.fill(0.0);
+        for i in -radius..=radius {
+            for (sum, c) in sums.iter_mut().zip(at(line, i).channels()) {
+                *sum += c.to_f64().unwrap_or(0.0);
+            }
+        }
+        for i in 0..len {
+            let (x, y) = if horizontal { (i, line) } else { (line, i) };
+            for (out, sum) in out.get_pixel_mut(x, y).channels_mut().iter_mut().zip(&sums) {
+                let average = sum / window;
+                let average = if integral { average.round() } else { average };
+                *out = num_traits::cast(average).unwrap_or(*out);
+            }
+
+            // Slide the window along by one pixel
+            let leaving = at(line, i as i64 - radius).channels();
+            let entering = at(line, i as i64 + radius + 1).channels();
+            for ((sum, l), e) in sums.iter_mut().zip(leaving).zip(entering) {
+                *sum += e.to_f64().unwrap_or(0.0) - l.to_f64().unwrap_or(0.0);
+            }
+        }
+    }
+
+    out
+}
+
+/// Gaussian blurs the gutters and background of a placed buffer, leaving the images sharp
+///
+/// Gives a frosted glass look where each image's colors bleed softly into the
+/// gaps around it. The whole buffer is blurred and then the images are drawn
+/// back over it, so `images` should be the blits the buffer was placed from.
+///
+/// # Arguments
+/// * `buffer` - ImageBuffer the blits were placed into
+/// * `images` - Blits that were placed into `buffer`, drawn back unblurred
+/// * `sigma` - Standard deviation of the gaussian in pixels
+///
+/// # Returns
+/// * `Result<(), ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{
+///     blur_gutters, get_concat_blits_with_options, place_images_in_buffer_with_pattern,
+///     ConcatDirection, ConcatOptions, Spacing,
+/// };
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let pattern = image::open("./test/3.png").unwrap().into_rgb8();
+/// let images = [img1, img2];
+/// let options = ConcatOptions {
+///     spacing: Spacing::Fixed(16),
+///     ..ConcatOptions::new(ConcatDirection::Vertical)
+/// };
+/// let blits = get_concat_blits_with_options(&images, &options, 0, 0);
+/// let mut img = place_images_in_buffer_with_pattern(&blits, &pattern).unwrap();
+/// blur_gutters(&mut img, &blits, 6.0).unwrap();
+/// ```
+pub fn blur_gutters<P: Pixel + 'static>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    images: &[ImageBlit<P>],
+    sigma: f32,
+) -> Result<(), ConcatError> {
+    let Placements { top_lefts, .. } = resolve_placements(images)?;
+    let blurred = blur(buffer, sigma);
+    *buffer = copy_blits_into(blurred, images, &top_lefts)?;
+    Ok(())
+}
+
+/// Soft shadow cast behind an image, see `ConcatOptions::shadow`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shadow<P: Pixel> {
+    /// Horizontal offset of the shadow from its image, positive is to the right
+    pub offset_x: i32,
+    /// Vertical offset of the shadow from its image, positive is down
+    pub offset_y: i32,
+    /// Box blur radius in pixels, 0 for a hard edged shadow
+    pub blur: u32,
+    pub color: P,
+    /// Strength of the shadow from 0.0 (invisible) to 1.0 (solid `color` away from its blurred edges)
+    pub opacity: f32,
+}
+
+/// Solid frame drawn around images
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border<P: Pixel> {
+    /// Thickness of the frame in pixels
+    pub width: u32,
+    pub color: P,
+}
+
+/// Places ImageBuffers into a single buffer, framing each image with a border
+///
+/// Same as `place_images_in_buffer`, but every image gets a `border.width` frame
+/// drawn just outside of it and the buffer grows to fit the frames. Frames are
+/// drawn before any image, so where neighbouring frames overlap an image, the
+/// image wins. Leaving a gap of `border.width` between images, e.g. with
+/// `ConcatOptions::spacing`, gives a single line between neighbours, and a gap of
+/// `2 * border.width` gives each image its own frame.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs which contain an ImageBuffer ref and
+///   target coordinate to place the image's origin
+/// * `border` - Width and color of the frame around each image
+/// * `canvas_border` - Also frame the whole buffer with another `border`
+///
+/// # Returns
+/// * `ImageBuffer` - Single ImageBuffer containing all framed images
+/// * `ConcatError::Empty` if no blits are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{place_images_in_buffer_with_border, Border, ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let border = Border { width: 4, color: image::Rgb([255, 255, 255]) };
+/// let blits = [ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, img1.width() + 4, 0)];
+/// let img_result = place_images_in_buffer_with_border(&blits, border, true);
+/// ```
+pub fn place_images_in_buffer_with_border<P: Pixel>(
+    images: &[ImageBlit<P>],
+    border: Border<P>,
+    canvas_border: bool,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let Placements {
+        dimensions: (width, height),
+        top_lefts,
+    } = resolve_placements(images)?;
+
+    // Everything is pushed in by the image frames, plus the canvas frame if requested
+    let pad = if canvas_border {
+        border.width.checked_mul(2)
+    } else {
+        Some(border.width)
+    }
+    .ok_or(ConcatError::DimensionOverflow)?;
+    let padded = |size: u32| pad.checked_mul(2).and_then(|pad| size.checked_add(pad));
+    let total_width = padded(width).ok_or(ConcatError::DimensionOverflow)?;
+    let total_height = padded(height).ok_or(ConcatError::DimensionOverflow)?;
+
+    let mut buffer = ImageBuffer::new(total_width, total_height);
+
+    if canvas_border {
+        let w = border.width;
+        fill_rect(&mut buffer, 0, 0, total_width, w, border.color);
+        fill_rect(
+            &mut buffer,
+            0,
+            total_height - w,
+            total_width,
+            w,
+            border.color,
+        );
+        fill_rect(&mut buffer, 0, 0, w, total_height, border.color);
+        fill_rect(
+            &mut buffer,
+            total_width - w,
+            0,
+            w,
+            total_height,
+            border.color,
+        );
+    }
+
+    // Draw every frame first so overlapping frames never cover an image
+    let top_lefts: Vec<_> = top_lefts.iter().map(|(x, y)| (x + pad, y + pad)).collect();
+    for (blit, (x, y)) in images.iter().zip(&top_lefts) {
+        let (width, height) = blit.dimensions();
+        fill_rect(
+            &mut buffer,
+            x - border.width,
+            y - border.width,
+            width + 2 * border.width,
+            height + 2 * border.width,
+            border.color,
+        );
+    }
+
+    copy_blits_into(buffer, images, &top_lefts)
+}
+
+/// Fills a rectangle of `buffer` with `color`
+fn fill_rect<P: Pixel>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    color: P,
+) {
+    for pixel_y in y..y + height {
+        for pixel_x in x..x + width {
+            buffer.put_pixel(pixel_x, pixel_y, color);
+        }
+    }
+}
+
+/// Computes the size of the buffer `place_images_in_buffer` would create for a set of blits
+///
+/// Useful for validating or reporting the output size before any memory is
+/// committed to the buffer itself.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBlit structs
+///
+/// # Returns
+/// * `Result<(width, height), ConcatError>` of the buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{blits_dimensions,ImageBlit};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let (width, height) = blits_dimensions(&[ImageBlit::new(&img1, 0, 0), ImageBlit::new(&img2, img1.width(), 0)]).unwrap();
+/// ```
+pub fn blits_dimensions<P: Pixel>(images: &[ImageBlit<P>]) -> Result<(u32, u32), ConcatError> {
+    Ok(resolve_placements(images)?.dimensions)
+}
+
+/// Computes the size of the buffer `concat_images` would create
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+///
+/// # Returns
+/// * `Result<(width, height), ConcatError>` of the concatenated image
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_dimensions, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let (width, height) = concat_dimensions(&[img1,img2], ConcatDirection::Vertical).unwrap();
+/// ```
+pub fn concat_dimensions<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    direction: ConcatDirection,
+) -> Result<(u32, u32), ConcatError> {
+    blits_dimensions(&get_concat_blits(images, direction, 0, 0))
+}
+
+/// Where each blit's top left lands in a buffer and the size of that buffer
+struct Placements {
+    dimensions: (u32, u32),
+    top_lefts: Vec<(u32, u32)>,
+}
+
+/// Resolves the top left coord of every blit along with the buffer size needed to contain them
+///
+/// Anchored blits that would land past the left or top edge grow the buffer, shifting every
+/// placement over by the same amount so relative positions are kept.
+fn resolve_placements<P: Pixel>(images: &[ImageBlit<P>]) -> Result<Placements, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    let top_lefts: Vec<_> = images.iter().map(|blit| blit.top_left()).collect();
+    let shift_x = top_lefts.iter().map(|(x, _)| *x).min().unwrap_or(0).min(0);
+    let shift_y = top_lefts.iter().map(|(_, y)| *y).min().unwrap_or(0).min(0);
+
+    let placements = top_lefts
+        .iter()
+        .map(|(x, y)| {
+            let x = u32::try_from(x - shift_x).map_err(|_| ConcatError::DimensionOverflow)?;
+            let y = u32::try_from(y - shift_y).map_err(|_| ConcatError::DimensionOverflow)?;
+            Ok((x, y))
+        })
+        .collect::<Result<Vec<_>, ConcatError>>()?;
+
+    // Each each images start point and dimensions to determine the total buffer size we'll need to contain everything
+    let dimensions = images
+        .iter()
+        .zip(&placements)
+        .try_fold((0, 0), |(max_width, max_height), (blit, (x, y))| {
+            Some((
+                max(max_width, x.checked_add(blit.dimensions().0)?),
+                max(max_height, y.checked_add(blit.dimensions().1)?),
+            ))
+        })
+        .ok_or(ConcatError::DimensionOverflow)?;
+
+    Ok(Placements {
+        dimensions,
+        top_lefts: placements,
+    })
+}
+
+/// Copies each blit into an already sized buffer at its resolved placement
+fn copy_blits_into<P: Pixel>(
+    mut buffer: ImageBuffer<P, Vec<P::Subpixel>>,
+    images: &[ImageBlit<P>],
+    top_lefts: &[(u32, u32)],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    // Copy each image into the final buffer, reading untransformed crops straight from the source
+    for (blit, (x, y)) in images.iter().zip(top_lefts) {
+        match blit.transform {
+            Transform::None => copy_image_into(&mut buffer, blit.img, blit.source(), *x, *y),
+            _ => {
+                let img = blit.image();
+                let (width, height) = img.dimensions();
+                let src = Rect {
+                    x: 0,
+                    y: 0,
+                    width,
+                    height,
+                };
+                copy_image_into(&mut buffer, &img, src, *x, *y);
+            }
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Copies the `src` region of `img` into `buffer` with its top left at (`x`, `y`),
+/// both of which must be in bounds
+///
+/// Goes through slice copies rather than the per pixel `GenericImage::copy_from`,
+/// so each row is a single memcpy. When the whole image spans the full width of
+/// the buffer, as every image of an evenly sized vertical concat does, its rows
+/// are back to back in both and the whole image is copied at once.
+fn copy_image_into<P: Pixel>(
+    buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>,
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    src: Rect,
+    x: u32,
+    y: u32,
+) {
+    let channels = P::CHANNEL_COUNT as usize;
+    let buffer_row_len = buffer.width() as usize * channels;
+    let img_row_len = img.width() as usize * channels;
+    let src_row_len = src.width as usize * channels;
+    if src_row_len == 0 || src.height == 0 {
+        return;
+    }
+
+    let start = y as usize * buffer_row_len + x as usize * channels;
+    let src_start = src.y as usize * img_row_len + src.x as usize * channels;
+    let src_len = (src.height as usize - 1) * img_row_len + src_row_len;
+    let src_raw = &img.as_raw()[src_start..src_start + src_len];
+    let raw: &mut [P::Subpixel] = buffer;
+    if src_row_len == img_row_len && img_row_len == buffer_row_len {
+        raw[start..start + src_raw.len()].copy_from_slice(src_raw);
+    } else {
+        for i in 0..src.height as usize {
+            let row = &src_raw[i * img_row_len..i * img_row_len + src_row_len];
+            let row_start = start + i * buffer_row_len;
+            raw[row_start..row_start + src_row_len].copy_from_slice(row);
+        }
+    }
+}
+
+/// Creates a Vector of ImageBlit structs
+///
+/// Takes start location and concat direction to create blits that will vertically or horizontally cocnatenate ImageBuffers
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `concat_direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `start_x` - x coord that the origin of the first image will be placed
+/// * `start_y` - y coord that the origin of the first image will be placed
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_concat_blits, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let blits = get_concat_blits(&[img1,img2], ConcatDirection::Vertical, 0, 0);
+/// ```
+pub fn get_concat_blits<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    concat_direction: ConcatDirection,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    get_concat_blits_aligned(images, concat_direction, Alignment::Start, start_x, start_y)
+}
+
+/// Creates a Vector of ImageBlit structs aligned along the cross axis
+///
+/// Same as `get_concat_blits`, but images smaller than the largest image on the
+/// cross axis (width for vertical, height for horizontal) are offset according
+/// to `alignment` instead of always hugging the leading edge.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `concat_direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `alignment` - Alignment::Start, Alignment::Center or Alignment::End
+/// * `start_x` - x coord that the origin of the first image will be placed
+/// * `start_y` - y coord that the origin of the first image will be placed
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_concat_blits_aligned, Alignment, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let blits = get_concat_blits_aligned(&[img1,img2], ConcatDirection::Vertical, Alignment::Center, 0, 0);
+/// ```
+pub fn get_concat_blits_aligned<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    concat_direction: ConcatDirection,
+    alignment: Alignment,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    let options = ConcatOptions {
+        alignment,
+        ..ConcatOptions::new(concat_direction)
+    };
+    get_concat_blits_with_options(images, &options, start_x, start_y)
+}
+
+/// Creates a Vector of ImageBlit structs according to a set of ConcatOptions
+///
+/// Handles direction, alignment, spacing and order. Every other option, such as
+/// the background, dividers or shadows, only applies once the blits are placed
+/// and is ignored here.
+///
+/// Blits are always returned in the same order as `images`, even when
+/// `Order::Reverse` places them right to left or bottom to top.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `options` - Direction, alignment, spacing and order of the concatenation
+/// * `start_x` - x coord of the leading edge of the concatenation
+/// * `start_y` - y coord that the origin of the first image will be placed
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_concat_blits_with_options, ConcatDirection, ConcatOptions, Spacing};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let options = ConcatOptions {
+///     // 5% of the average image width
+///     spacing: Spacing::Percent(5.0),
+///     ..ConcatOptions::<image::Rgb<u8>>::new(ConcatDirection::Horizontal)
+/// };
+/// let blits = get_concat_blits_with_options(&[img1,img2], &options, 0, 0);
+/// ```
+pub fn get_concat_blits_with_options<'a, P: Pixel>(
+    images: &'a [ImageBuffer<P, Vec<P::Subpixel>>],
+    options: &ConcatOptions<P>,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'a, P>> {
+    let ConcatOptions {
+        direction,
+        alignment,
+        spacing,
+        order,
+        ..
+    } = *options;
+    let spacing = spacing.pixels(images, direction);
+
+    // Largest cross axis dimension which all images are aligned within
+    let cross_extent = images
+        .iter()
+        .map(|img| match direction {
+            ConcatDirection::Vertical => img.width(),
+            ConcatDirection::Horizontal => img.height(),
+        })
+        .max()
+        .unwrap_or(0);
+
+    // Strep through each image and create an ImageBlit with start relative to the previous image's width or height depending on the concat direction
+    // Coords saturate rather than wrap so an overflowing plan is caught when the blits are placed
+    let (mut blits, _) = images.iter().fold(
+        (Vec::new(), (start_x, start_y)),
+        |(mut blits, (x, y)), img| match direction {
+            ConcatDirection::Vertical => {
+                let offset = alignment.offset(cross_extent, img.width());
+                blits.push(ImageBlit::new(img, x.saturating_add(offset), y));
+                (
+                    blits,
+                    (x, y.saturating_add(img.height()).saturating_add(spacing)),
+                )
+            }
+            ConcatDirection::Horizontal => {
+                let offset = alignment.offset(cross_extent, img.height());
+                blits.push(ImageBlit::new(img, x, y.saturating_add(offset)));
+                (
+                    blits,
+                    (x.saturating_add(img.width()).saturating_add(spacing), y),
+                )
+            }
+        },
+    );
+
+    // Mirror the forward layout along the main axis so the first image lands at the far end
+    if order == Order::Reverse {
+        let main_end = blits
+            .iter()
+            .map(|blit| match direction {
+                ConcatDirection::Vertical => blit.y.saturating_add(blit.dimensions().1),
+                ConcatDirection::Horizontal => blit.x.saturating_add(blit.dimensions().0),
+            })
+            .max()
+            .unwrap_or(0);
+        for blit in &mut blits {
+            match direction {
+                ConcatDirection::Vertical => {
+                    blit.y = start_y.saturating_add(
+                        main_end.saturating_sub(blit.y.saturating_add(blit.dimensions().1)),
+                    );
+                }
+                ConcatDirection::Horizontal => {
+                    blit.x = start_x.saturating_add(
+                        main_end.saturating_sub(blit.x.saturating_add(blit.dimensions().0)),
+                    );
+                }
+            }
+        }
+    }
+
+    blits
+}
+
+/// Combines several blit plans into one so they can be placed in a single buffer
+///
+/// Blits keep their coordinates, so plans created with different start points,
+/// directions or layouts end up side by side in the same buffer. Later plans are
+/// drawn over earlier ones where they overlap.
+///
+/// # Arguments
+/// * `plans` - Slice of blit plans, e.g. from `get_concat_blits`
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_concat_blits, merge_blit_plans, place_images_in_buffer, ConcatDirection};
+/// let imgs: Vec<_> = (1..=4)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let column_height = imgs[0].height() + imgs[1].height();
+///
+/// // two images stacked at x=0, then two side by side at x=200 beneath them
+/// let stacked = get_concat_blits(&imgs[..2], ConcatDirection::Vertical, 0, 0);
+/// let row = get_concat_blits(&imgs[2..], ConcatDirection::Horizontal, 200, column_height);
+/// let blits = merge_blit_plans(&[stacked, row]);
+///
+/// let img = place_images_in_buffer(&blits).unwrap();
+/// assert_eq!(img.width(), 200 + imgs[2].width() + imgs[3].width());
+/// assert_eq!(img.height(), column_height + imgs[2].height().max(imgs[3].height()));
+/// ```
+pub fn merge_blit_plans<'a, P: Pixel>(plans: &[Vec<ImageBlit<'a, P>>]) -> Vec<ImageBlit<'a, P>> {
+    plans.iter().flatten().copied().collect()
+}
+
+/// Lazily creates ImageBlit structs for horizontal or vertical concatenation
+///
+/// Same placements as `get_concat_blits`, but computed as the iterator is advanced
+/// instead of collected into a Vec. Plans can be chained together, e.g. a
+/// horizontal run followed by a vertical one, without any intermediate allocation.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `concat_direction` - ConcatDirection::Vertical or ConcatDirection::Horizontal
+/// * `start_x` - x coord that the origin of the first image will be placed
+/// * `start_y` - y coord that the origin of the first image will be placed
+///
+/// # Returns
+/// * Iterator of ImageBlit structs
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{concat_blit_iter, place_images_in_buffer, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let row = [img1, img2];
+/// // a row of images followed by the same images in a column beneath it
+/// let blits: Vec<_> = concat_blit_iter(&row, ConcatDirection::Horizontal, 0, 0)
+///     .chain(concat_blit_iter(&row, ConcatDirection::Vertical, 0, row[0].height()))
+///     .collect();
+/// let img_result = place_images_in_buffer(&blits);
+/// ```
+pub fn concat_blit_iter<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    concat_direction: ConcatDirection,
+    start_x: u32,
+    start_y: u32,
+) -> impl Iterator<Item = ImageBlit<'_, P>> {
+    ConcatBlitIter {
+        images: images.iter(),
+        direction: concat_direction,
+        x: start_x,
+        y: start_y,
+    }
+}
+
+/// Iterator behind `concat_blit_iter`, carrying the cursor for the next image
+struct ConcatBlitIter<'a, P: Pixel> {
+    images: std::slice::Iter<'a, ImageBuffer<P, Vec<P::Subpixel>>>,
+    direction: ConcatDirection,
+    x: u32,
+    y: u32,
+}
+
+impl<'a, P: Pixel> Iterator for ConcatBlitIter<'a, P> {
+    type Item = ImageBlit<'a, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let img = self.images.next()?;
+        let blit = ImageBlit::new(img, self.x, self.y);
+
+        // Saturate like get_concat_blits so an overflowing plan is caught when placed
+        match self.direction {
+            ConcatDirection::Vertical => self.y = self.y.saturating_add(img.height()),
+            ConcatDirection::Horizontal => self.x = self.x.saturating_add(img.width()),
+        }
+
+        Some(blit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.images.size_hint()
+    }
+}
+
+/// Horizontally concatenates images, wrapping onto new rows to stay within a max width
+///
+/// Images are placed left to right until the next image would push the row past
+/// `max_width`, at which point it starts a new row below. Each row is as tall as
+/// its tallest image. An image wider than `max_width` is placed on a row of its own.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `max_width` - Maximum width of a row
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::concat_images_wrapped;
+/// let imgs: Vec<_> = (1..=8)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// // test images are 422px wide so this will have 2 images per row
+/// let img_result = concat_images_wrapped(&imgs, 1000);
+/// ```
+pub fn concat_images_wrapped<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    max_width: u32,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let blits = get_wrapped_blits(images, max_width, 0, 0);
+    place_images_in_buffer(&blits)
+}
+
+/// Creates a Vector of ImageBlit structs that wrap onto new rows to stay within a max width
+///
+/// See `concat_images_wrapped` for how rows are wrapped.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate
+/// * `max_width` - Maximum width of a row, measured from `start_x`
+/// * `start_x` - x coord of the start of every row
+/// * `start_y` - y coord of the first row
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::get_wrapped_blits;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let blits = get_wrapped_blits(&[img1,img2], 500, 0, 0);
+/// ```
+pub fn get_wrapped_blits<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    max_width: u32,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    let mut blits = Vec::with_capacity(images.len());
+
+    // Cursor within the current row and the tallest image seen in it
+    let (mut x, mut y) = (0u32, start_y);
+    let mut row_height = 0;
+    for img in images {
+        // Wrap to a new row unless this would be the first image in the row
+        if x > 0 && x.saturating_add(img.width()) > max_width {
+            x = 0;
+            y = y.saturating_add(row_height);
+            row_height = 0;
+        }
+
+        blits.push(ImageBlit::new(img, start_x.saturating_add(x), y));
+        x = x.saturating_add(img.width());
+        row_height = max(row_height, img.height());
+    }
+
+    blits
+}
+
+/// Where each named sprite of a sheet from `pack_sprite_sheet` was placed
+///
+/// With the `serde` feature enabled this can be serialized, e.g. to JSON for a
+/// game engine to load alongside the sheet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AtlasMap {
+    pub width: u32,
+    pub height: u32,
+    /// Region of the sheet each sprite occupies, by name
+    pub sprites: BTreeMap<String, Rect>,
+}
+
+/// An image and the name it's packed under by `pack_sprite_sheet`
+pub type NamedImage<P> = (String, ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>);
+
+/// A packed sprite sheet and the atlas describing where each sprite is
+pub type SpriteSheet<P> = (ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>, AtlasMap);
+
+/// Packs named images into a sprite sheet, returning the sheet and where each sprite landed
+///
+/// Sprites are packed largest first with the MaxRects algorithm into a sheet
+/// `max_width` wide, each as near the top as it fits, so mixed sizes fill in
+/// each other's gaps rather than leaving ragged rows. A sprite wider than
+/// `max_width` widens the sheet to fit. The sheet is then trimmed to the
+/// sprites, and anything between them is transparent for RGBA sheets. If two
+/// sprites share a name both are packed, but only the later one is recorded
+/// in the atlas. See `pack_bins` to pack into fixed size textures instead.
+///
+/// # Arguments
+/// * `named_images` - Slice of names and the ImageBuffers to pack under them
+/// * `max_width` - Width of the sheet
+///
+/// # Returns
+/// * `Result<SpriteSheet, ConcatError>` - The sheet and its `AtlasMap`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::pack_sprite_sheet;
+/// let sprites: Vec<_> = ["idle", "walk", "jump"]
+///     .iter()
+///     .map(|name| (name.to_string(), image::RgbaImage::new(16, 16)))
+///     .collect();
+/// let (sheet, atlas) = pack_sprite_sheet(&sprites, 32).unwrap();
+/// assert_eq!(atlas.sprites["jump"].y, 16);
+/// ```
+pub fn pack_sprite_sheet<P: Pixel>(
+    named_images: &[NamedImage<P>],
+    max_width: u32,
+) -> Result<SpriteSheet<P>, ConcatError> {
+    let sizes: Vec<_> = named_images
+        .iter()
+        .map(|(_, img)| img.dimensions())
+        .collect();
+    let width = sizes.iter().map(|(width, _)| *width).fold(max_width, max);
+
+    // The sheet is as tall as it needs to be, so only running out of u32 stops a sprite fitting
+    let mut bin = pack::MaxRects::new(width, u32::MAX, pack::Heuristic::BottomLeft);
+    let mut rects = vec![Rect::default(); sizes.len()];
+    for index in pack::packing_order(&sizes) {
+        let (width, height) = sizes[index];
+        rects[index] = bin
+            .insert(width, height)
+            .ok_or(ConcatError::DimensionOverflow)?;
+    }
+
+    let blits: Vec<_> = named_images
+        .iter()
+        .zip(&rects)
+        .map(|((_, img), rect)| ImageBlit::new(img, rect.x, rect.y))
+        .collect();
+    let sheet = place_images_in_buffer(&blits)?;
+
+    let sprites = named_images
+        .iter()
+        .zip(rects)
+        .map(|((name, _), rect)| (name.clone(), rect))
+        .collect();
+    let atlas = AtlasMap {
+        width: sheet.width(),
+        height: sheet.height(),
+        sprites,
+    };
+    Ok((sheet, atlas))
+}
+
+/// Scales and packs images into justified rows of a fixed width, like a photo gallery
+///
+/// Images are scaled to `target_row_height` and packed left to right into rows.
+/// A row is closed at whichever break leaves its height closest to the target,
+/// then the whole row is scaled so it fills `max_width` exactly, stretching or
+/// shrinking its height. The last image of each row absorbs any rounding so the
+/// right edge is flush. The final row is left at `target_row_height` rather than
+/// being stretched to fill the width. This requires a resized copy of every image.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to pack
+/// * `target_row_height` - Height rows are scaled towards
+/// * `max_width` - Width every full row is scaled to fill
+/// * `filter` - Sampling filter used when resizing
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::masonry_concat_images;
+/// use image::imageops::FilterType;
+/// let imgs: Vec<_> = (1..=8)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = masonry_concat_images(&imgs, 150, 800, FilterType::Triangle);
+/// ```
+pub fn masonry_concat_images<P: Pixel + 'static>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    target_row_height: u32,
+    max_width: u32,
+    filter: FilterType,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    // Width of each image when scaled to the target height
+    let target = target_row_height as f64;
+    let widths: Vec<_> = images
+        .iter()
+        .map(|img| img.width() as f64 * target / max(img.height(), 1) as f64)
+        .collect();
+
+    // Rows as (images, width at the target height, justified), closing a row either before
+    // or after the image that overflows it, whichever needs less scaling to fill the width
+    let mut rows = Vec::new();
+    let (mut start, mut row_width) = (0, 0.0);
+    for (idx, width) in widths.iter().enumerate() {
+        if row_width + width <= max_width as f64 {
+            row_width += width;
+            continue;
+        }
+
+        let shrink = (row_width + width) / max_width as f64;
+        let stretch = max_width as f64 / row_width;
+        if idx > start && stretch <= shrink {
+            rows.push((start..idx, row_width, true));
+            (start, row_width) = (idx, *width);
+        } else {
+            rows.push((start..idx + 1, row_width + width, true));
+            (start, row_width) = (idx + 1, 0.0);
+        }
+    }
+
+    // The final row keeps the target height instead of being stretched to fill
+    if start < images.len() {
+        rows.push((start..images.len(), row_width, false));
+    }
+
+    let mut scaled = Vec::with_capacity(images.len());
+    let mut placements = Vec::with_capacity(images.len());
+    let mut y = 0u32;
+    for (range, width_at_target, justified) in rows {
+        let row_height = if justified {
+            max(
+                (target * max_width as f64 / width_at_target).round() as u32,
+                1,
+            )
+        } else {
+            target_row_height
+        };
+
+        let mut x = 0u32;
+        for idx in range.clone() {
+            let img = &images[idx];
+            let width = if justified && idx == range.end - 1 {
+                // Last image fills whatever is left so the row ends exactly at max_width
+                max(max_width.saturating_sub(x), 1)
+            } else {
+                scale_dimension(img.width(), row_height, max(img.height(), 1))
+            };
+
+            scaled.push(imageops::resize(img, width, row_height, filter));
+            placements.push((x, y));
+            x = x.saturating_add(width);
+        }
+        y = y.saturating_add(row_height);
+    }
+
+    let blits: Vec<_> = scaled
+        .iter()
+        .zip(placements)
+        .map(|(img, (x, y))| ImageBlit::new(img, x, y))
+        .collect();
+    place_images_in_buffer(&blits)
+}
+
+/// Cascades images, shifting each image by a fixed offset from the previous one
+///
+/// The first image is placed in the corner the cascade moves away from, e.g. the
+/// top left for positive `dx` and `dy` or the top right for a negative `dx`, and
+/// later images are drawn over earlier ones. The buffer grows to fit the whole
+/// cascade.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to cascade
+/// * `dx` - Horizontal offset of each image from the previous one
+/// * `dy` - Vertical offset of each image from the previous one
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::cascade_concat_images;
+/// let imgs: Vec<_> = (1..=3)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = cascade_concat_images(&imgs, 20, 20);
+/// ```
+pub fn cascade_concat_images<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    dx: i32,
+    dy: i32,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    // Start far enough in that negative offsets never step past the left or top edge
+    let steps = images.len().saturating_sub(1) as u64;
+    let start = |offset: i32| {
+        let distance = if offset < 0 {
+            offset.unsigned_abs() as u64 * steps
+        } else {
+            0
+        };
+        u32::try_from(distance).map_err(|_| ConcatError::DimensionOverflow)
+    };
+
+    let blits = get_cascade_blits(images, dx, dy, start(dx)?, start(dy)?);
+    place_images_in_buffer(&blits)
+}
+
+/// Creates a Vector of ImageBlit structs that cascade with a fixed offset between images
+///
+/// See `cascade_concat_images`. Coordinates that would step past 0 are clamped to 0,
+/// so the start point should leave room for negative offsets.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to cascade
+/// * `dx` - Horizontal offset of each image from the previous one
+/// * `dy` - Vertical offset of each image from the previous one
+/// * `start_x` - x coord that the origin of the first image will be placed
+/// * `start_y` - y coord that the origin of the first image will be placed
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::get_cascade_blits;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let blits = get_cascade_blits(&[img1,img2], -20, 20, 20, 0);
+/// ```
+pub fn get_cascade_blits<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    dx: i32,
+    dy: i32,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    let step = |start: u32, offset: i32, idx: usize| {
+        let coord = start as i64 + offset as i64 * idx as i64;
+        coord.clamp(0, u32::MAX as i64) as u32
+    };
+
+    images
+        .iter()
+        .enumerate()
+        .map(|(idx, img)| ImageBlit::new(img, step(start_x, dx, idx), step(start_y, dy, idx)))
+        .collect()
+}
+
+/// Horizontally concatenates images with each one overlapping the previous, blending the seams
+///
+/// Meant for stitching scans or photos that share content along their edges.
+/// Each image starts `overlap_px` pixels before the end of the previous one and
+/// the overlapping columns fade linearly from the left image to the right one,
+/// so the output is narrower than `concat_images` by the total overlap. The
+/// overlap is limited to the width of the narrower of each pair of images.
+/// Images are top aligned, and where only one image of a pair is tall enough to
+/// cover the overlap it's used as is.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to stitch left to right
+/// * `overlap_px` - Number of columns shared by adjacent images
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::panorama_concat;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = panorama_concat(&[img1,img2], 10);
+/// ```
+pub fn panorama_concat<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    overlap_px: u32,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let overlaps: Vec<u32> = images
+        .windows(2)
+        .map(|pair| overlap_px.min(pair[0].width()).min(pair[1].width()))
+        .collect();
+
+    let mut blits = Vec::with_capacity(images.len());
+    let mut x: u32 = 0;
+    for (idx, img) in images.iter().enumerate() {
+        if idx > 0 {
+            // Each overlap is at most the previous width, so this never steps back past its start
+            x = x
+                .checked_add(images[idx - 1].width() - overlaps[idx - 1])
+                .ok_or(ConcatError::DimensionOverflow)?;
+        }
+        blits.push(ImageBlit::new(img, x, 0));
+    }
+    // Later images are drawn over earlier ones, so only the overlaps need redrawing
+    let mut buffer = place_images_in_buffer(&blits)?;
+
+    for (idx, &overlap) in overlaps.iter().enumerate() {
+        let (left, right) = (&images[idx], &images[idx + 1]);
+        let left_start = left.width() - overlap;
+        let x = blits[idx + 1].x;
+        for i in 0..overlap {
+            // Weight of the right image, rising from 0 at the first shared column
+            let t = i as f32 / overlap as f32;
+            for y in 0..left.height().min(right.height()) {
+                let pixel = left
+                    .get_pixel(left_start + i, y)
+                    .map2(right.get_pixel(i, y), |a, b| lerp(a, b, t));
+                buffer.put_pixel(x + i, y, pixel);
+            }
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Linearly interpolates from `a` at `t = 0` to `b` at `t = 1`
+fn lerp<T: image::Primitive>(a: T, b: T, t: f32) -> T {
+    let a_value = a.to_f32().unwrap_or_default();
+    let b_value = b.to_f32().unwrap_or_default();
+    num_traits::cast(a_value + (b_value - a_value) * t).unwrap_or(a)
+}
+
+/// Concatenates images into a grid
+///
+/// Images are laid out left to right, top to bottom into fixed grid cells. Each
+/// row is as tall as its tallest image and each column is as wide as its widest
+/// image, so every image in a row shares the same y coord and every image in a
+/// column shares the same x coord.
+///
+/// Unlike `column_concat_images`, which packs a variable number of images into
+/// each column, every row here holds `columns` images except for a partially
+/// filled final row.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::grid_concat_images;
+/// let imgs: Vec<_> = (1..=5)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = grid_concat_images(&imgs, 2);
+/// ```
+pub fn grid_concat_images<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let blits = get_grid_blits(images, columns, 0, 0);
+    place_images_in_buffer(&blits)
+}
+
+/// Concatenates images into a grid, aligning images shorter than their row
+///
+/// Same as `grid_concat_images`, which places every image at the top of its
+/// row, but `row_align` controls where shorter images sit within the row's
+/// height, e.g. `RowAlign::Middle` to center images of mixed heights.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `row_align` - Vertical placement of images within their row
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{grid_concat_images_aligned, RowAlign};
+/// let imgs: Vec<_> = (1..=5)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = grid_concat_images_aligned(&imgs, 2, RowAlign::Middle);
+/// ```
+pub fn grid_concat_images_aligned<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    row_align: RowAlign,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let blits = get_grid_blits_aligned(images, columns, row_align, 0, 0);
+    place_images_in_buffer(&blits)
+}
+
+/// Shape of the grid `montage` lays images out in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MontageTarget {
+    /// As close to square as possible, with at least as many columns as rows
+    #[default]
+    AutoSquare,
+    /// A fixed number of columns, adding rows as needed
+    FixedCols(usize),
+    /// A fixed number of rows, adding columns as needed
+    FixedRows(usize),
+}
+
+impl MontageTarget {
+    /// Number of columns to lay `count` images out in
+    fn columns(self, count: usize) -> usize {
+        match self {
+            MontageTarget::AutoSquare => (count as f64).sqrt().ceil() as usize,
+            MontageTarget::FixedCols(columns) => columns,
+            MontageTarget::FixedRows(0) => 0,
+            MontageTarget::FixedRows(rows) => count.div_ceil(rows),
+        }
+    }
+}
+
+/// Places images in a grid shaped by `target`, for when any reasonable montage will do
+///
+/// The column count is worked out from the number of images, then the images are
+/// laid out the same as `grid_concat_images`. `MontageTarget::FixedRows` can end up
+/// with fewer rows than asked for, e.g. 9 images in 4 rows need 3 columns, which
+/// only fill 3 rows.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `target` - How the grid's rows and columns are chosen
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::ColumnCountZero` if the target has zero columns or rows
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{montage, MontageTarget};
+/// let imgs: Vec<_> = (1..=5)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = montage(&imgs, MontageTarget::AutoSquare);
+/// ```
+pub fn montage<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    target: MontageTarget,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    grid_concat_images(images, target.columns(images.len()))
+}
+
+/// Places four images in a 2x2 grid
+///
+/// The left column is as wide as the wider of `top_left` and `bottom_left`,
+/// and the top row as tall as the taller of `top_left` and `top_right`, so
+/// each image sits at the top left of its quadrant with any padding zero
+/// filled. Equivalent to `grid_concat_images` with 2 columns, without needing
+/// the images in a slice.
+///
+/// # Arguments
+/// * `top_left`, `top_right`, `bottom_left`, `bottom_right` - ImageBuffer for each quadrant
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::concat_2x2;
+/// let imgs: Vec<_> = (1..=4)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = concat_2x2(&imgs[0], &imgs[1], &imgs[2], &imgs[3]);
+/// ```
+pub fn concat_2x2<P: Pixel>(
+    top_left: &ImageBuffer<P, Vec<P::Subpixel>>,
+    top_right: &ImageBuffer<P, Vec<P::Subpixel>>,
+    bottom_left: &ImageBuffer<P, Vec<P::Subpixel>>,
+    bottom_right: &ImageBuffer<P, Vec<P::Subpixel>>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let left_width = max(top_left.width(), bottom_left.width());
+    let top_height = max(top_left.height(), top_right.height());
+
+    place_images_in_buffer(&[
+        ImageBlit::new(top_left, 0, 0),
+        ImageBlit::new(top_right, left_width, 0),
+        ImageBlit::new(bottom_left, 0, top_height),
+        ImageBlit::new(bottom_right, left_width, top_height),
+    ])
+}
+
+/// Which lines of a grid `Stripes` alternate between
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StripeAxis {
+    /// Each row of cells is one band
+    #[default]
+    Rows,
+    /// Each column of cells is one band
+    Columns,
+}
+
+/// Alternating background colors behind the rows or columns of a grid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stripes<P: Pixel> {
+    pub axis: StripeAxis,
+    /// Background of the first, third, fifth... row or column
+    pub even: P,
+    /// Background of the second, fourth, sixth... row or column
+    pub odd: P,
+}
+
+/// Concatenates images into a grid with zebra striped rows or columns
+///
+/// Same as `grid_concat_images`, but the gaps around images smaller than their
+/// cell are filled with alternating `stripes` colors, one per row or column, so
+/// a faint stripe makes a tabular layout easier to follow across.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `stripes` - Axis and colors of the bands
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{grid_concat_images_striped, StripeAxis, Stripes};
+/// let imgs: Vec<_> = (1..=5)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let stripes = Stripes {
+///     axis: StripeAxis::Rows,
+///     even: image::Rgb([255, 255, 255]),
+///     odd: image::Rgb([235, 235, 235]),
+/// };
+/// let img_result = grid_concat_images_striped(&imgs, 2, stripes);
+/// ```
+pub fn grid_concat_images_striped<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    stripes: Stripes<P>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let blits = get_grid_blits(images, columns, 0, 0);
+    let Placements {
+        dimensions: (width, height),
+        top_lefts,
+    } = resolve_placements(&blits)?;
+
+    let (col_widths, row_heights) = grid_cell_sizes(
+        images.iter().map(|img| img.dimensions()),
+        columns,
+        FillOrder::RowMajor,
+    );
+    let bands = match stripes.axis {
+        StripeAxis::Rows => row_heights,
+        StripeAxis::Columns => col_widths,
+    };
+    // Band each pixel falls in along the striped axis
+    let band_of: Vec<_> = bands
+        .iter()
+        .enumerate()
+        .flat_map(|(band, size)| std::iter::repeat_n(band, *size as usize))
+        .collect();
+
+    let buffer = ImageBuffer::from_fn(width, height, |x, y| {
+        let along = match stripes.axis {
+            StripeAxis::Rows => y,
+            StripeAxis::Columns => x,
+        };
+        if band_of[along as usize] % 2 == 0 {
+            stripes.even
+        } else {
+            stripes.odd
+        }
+    });
+    copy_blits_into(buffer, &blits, &top_lefts)
+}
+
+/// Order images fill the cells of a grid in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillOrder {
+    /// Left to right along each row, then down to the next row
+    #[default]
+    RowMajor,
+    /// Top to bottom down each column, then across to the next column
+    ColumnMajor,
+}
+
+impl FillOrder {
+    /// Row and column of the cell image `idx` lands in, in a grid of `columns` by `rows`
+    fn cell(self, idx: usize, columns: usize, rows: usize) -> (usize, usize) {
+        match self {
+            FillOrder::RowMajor => (idx / columns, idx % columns),
+            FillOrder::ColumnMajor => (idx % rows, idx / rows),
+        }
+    }
+}
+
+/// Concatenates images into a grid, filling it row by row or column by column
+///
+/// Same as `grid_concat_images` with `FillOrder::RowMajor`. With
+/// `FillOrder::ColumnMajor` the grid has as many rows as a row major fill would,
+/// and images run down each column in turn, so with 6 images in 3 columns
+/// the first column holds images 0 and 1. The last column may be partially
+/// filled, and when the images run out early fewer than `columns` are used,
+/// e.g. 5 images in 4 columns need 2 rows, which only fill 3 columns.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `fill` - Whether images fill rows or columns first
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{grid_concat_images_with_fill, FillOrder};
+/// let imgs: Vec<_> = (1..=6)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = grid_concat_images_with_fill(&imgs, 3, FillOrder::ColumnMajor);
+/// ```
+pub fn grid_concat_images_with_fill<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    fill: FillOrder,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let blits = get_grid_blits_with_fill(images, columns, fill, 0, 0);
+    place_images_in_buffer(&blits)
+}
+
+/// Creates a Vector of ImageBlit structs laid out in a grid filled in the given order
+///
+/// See `grid_concat_images_with_fill`.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `fill` - Whether images fill rows or columns first
+/// * `start_x` - x coord of the top left of the grid
+/// * `start_y` - y coord of the top left of the grid
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Panics
+/// * If `columns` is 0
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_grid_blits_with_fill, FillOrder};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img3 = image::open("./test/3.png").unwrap().into_rgb8();
+/// let blits = get_grid_blits_with_fill(&[img1,img2,img3], 2, FillOrder::ColumnMajor, 0, 0);
+/// ```
+pub fn get_grid_blits_with_fill<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    fill: FillOrder,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    grid_blits(images, columns, fill, RowAlign::Top, start_x, start_y)
+}
+
+/// Creates a Vector of ImageBlit structs laid out in a grid
+///
+/// See `grid_concat_images` for how cells are sized.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `start_x` - x coord of the top left of the grid
+/// * `start_y` - y coord of the top left of the grid
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Panics
+/// * If `columns` is 0
+///
+/// # Example
+/// ```
+/// use image_concat_rs::get_grid_blits;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img3 = image::open("./test/3.png").unwrap().into_rgb8();
+/// let blits = get_grid_blits(&[img1,img2,img3], 2, 0, 0);
+/// ```
+pub fn get_grid_blits<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    get_grid_blits_aligned(images, columns, RowAlign::Top, start_x, start_y)
+}
+
+/// Creates a Vector of ImageBlit structs laid out in a grid with aligned rows
+///
+/// See `grid_concat_images_aligned`.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `row_align` - Vertical placement of images within their row
+/// * `start_x` - x coord of the top left of the grid
+/// * `start_y` - y coord of the top left of the grid
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Panics
+/// * If `columns` is 0
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_grid_blits_aligned, RowAlign};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img3 = image::open("./test/3.png").unwrap().into_rgb8();
+/// let blits = get_grid_blits_aligned(&[img1,img2,img3], 2, RowAlign::Bottom, 0, 0);
+/// ```
+pub fn get_grid_blits_aligned<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    row_align: RowAlign,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    grid_blits(
+        images,
+        columns,
+        FillOrder::RowMajor,
+        row_align,
+        start_x,
+        start_y,
+    )
+}
+
+/// Shared layout of the grid blit functions, filling cells in `fill` order
+fn grid_blits<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    fill: FillOrder,
+    row_align: RowAlign,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'_, P>> {
+    assert!(columns > 0, "grid needs at least one column");
+    let rows = images.len().div_ceil(columns);
+    let (col_widths, row_heights) =
+        grid_cell_sizes(images.iter().map(|img| img.dimensions()), columns, fill);
+
+    // Running sums of the cell sizes give us each cell's origin
+    let col_xs: Vec<_> = col_widths
+        .iter()
+        .scan(start_x, |x, width| {
+            let col_x = *x;
+            *x = x.saturating_add(*width);
+            Some(col_x)
+        })
+        .collect();
+    let row_ys: Vec<_> = row_heights
+        .iter()
+        .scan(start_y, |y, height| {
+            let row_y = *y;
+            *y = y.saturating_add(*height);
+            Some(row_y)
+        })
+        .collect();
+
+    images
+        .iter()
+        .enumerate()
+        .map(|(idx, img)| {
+            let (row, col) = fill.cell(idx, columns, rows);
+            let offset = Alignment::from(row_align).offset(row_heights[row], img.height());
+            ImageBlit::new(img, col_xs[col], row_ys[row].saturating_add(offset))
+        })
+        .collect()
+}
+
+/// Block of cells an image covers in `grid_concat_spans`, counted in cells from the top left
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GridSpan {
+    pub col: u32,
+    pub row: u32,
+    /// Number of columns covered, from `col` rightwards
+    pub colspan: u32,
+    /// Number of rows covered, from `row` downwards
+    pub rowspan: u32,
+}
+
+impl GridSpan {
+    /// Span covering the single cell at `col`, `row`
+    pub fn cell(col: u32, row: u32) -> Self {
+        GridSpan {
+            col,
+            row,
+            colspan: 1,
+            rowspan: 1,
+        }
+    }
+
+    /// Pixel region of the span in a grid of `cell_width` x `cell_height` cells, saturating at u32::MAX
+    fn rect(&self, cell_width: u32, cell_height: u32) -> Rect {
+        Rect {
+            x: self.col.saturating_mul(cell_width),
+            y: self.row.saturating_mul(cell_height),
+            width: self.colspan.saturating_mul(cell_width),
+            height: self.rowspan.saturating_mul(cell_height),
+        }
+    }
+}
+
+/// Places images in a grid of equal cells where each image may span several cells
+///
+/// Collage layouts often give a hero image a larger block, e.g. a 2x2 span with
+/// single cell images around it. Each image is paired with the span at the same
+/// index, any images or spans without a partner are ignored. Images are placed at
+/// the top left of their span and cropped to it, resize them beforehand to fill it.
+/// The output covers every span and anything not covered by an image is zero
+/// filled. Spans may overlap, in which case later images are drawn on top.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place
+/// * `spans` - Cells each image covers
+/// * `cell_width` - Width of a single cell in pixels
+/// * `cell_height` - Height of a single cell in pixels
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::Empty` if there are no images or spans
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{grid_concat_spans, GridSpan};
+/// let imgs: Vec<_> = (1..=3)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let hero = GridSpan { col: 0, row: 0, colspan: 2, rowspan: 2 };
+/// let spans = [hero, GridSpan::cell(2, 0), GridSpan::cell(2, 1)];
+/// let img_result = grid_concat_spans(&imgs, &spans, 211, 16);
+/// ```
+pub fn grid_concat_spans<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    spans: &[GridSpan],
+    cell_width: u32,
+    cell_height: u32,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    let count = images.len().min(spans.len());
+    if count == 0 {
+        return Err(ConcatError::Empty);
+    }
+
+    let (width, height) = spans[..count]
+        .iter()
+        .try_fold((0u32, 0u32), |(width, height), span| {
+            let right = span
+                .col
+                .checked_add(span.colspan)?
+                .checked_mul(cell_width)?;
+            let bottom = span
+                .row
+                .checked_add(span.rowspan)?
+                .checked_mul(cell_height)?;
+            Some((max(width, right), max(height, bottom)))
+        })
+        .ok_or(ConcatError::DimensionOverflow)?;
+
+    let blits = get_span_blits(images, spans, cell_width, cell_height, 0, 0);
+    let top_lefts: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+    copy_blits_into(ImageBuffer::new(width, height), &blits, &top_lefts)
+}
+
+/// Creates a Vector of ImageBlit structs placing each image at the top left of its GridSpan
+///
+/// See `grid_concat_spans`. Each blit's `src_rect` crops its image to the span.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place
+/// * `spans` - Cells each image covers
+/// * `cell_width` - Width of a single cell in pixels
+/// * `cell_height` - Height of a single cell in pixels
+/// * `start_x` - x coord of the top left of the grid
+/// * `start_y` - y coord of the top left of the grid
+///
+/// # Returns
+/// * Vec of ImageBlit structs that can be passed to place_images_in_buffer to draw all images to a single buffer
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{get_span_blits, GridSpan};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let spans = [GridSpan { col: 0, row: 0, colspan: 1, rowspan: 2 }, GridSpan::cell(1, 0)];
+/// let blits = get_span_blits(&[img1, img2], &spans, 422, 16, 0, 0);
+/// ```
+pub fn get_span_blits<'a, P: Pixel>(
+    images: &'a [ImageBuffer<P, Vec<P::Subpixel>>],
+    spans: &[GridSpan],
+    cell_width: u32,
+    cell_height: u32,
+    start_x: u32,
+    start_y: u32,
+) -> Vec<ImageBlit<'a, P>> {
+    images
+        .iter()
+        .zip(spans)
+        .map(|(img, span)| {
+            let rect = span.rect(cell_width, cell_height);
+            let crop = Rect {
+                x: 0,
+                y: 0,
+                width: rect.width,
+                height: rect.height,
+            };
+            let x = start_x.saturating_add(rect.x);
+            let y = start_y.saturating_add(rect.y);
+            ImageBlit::new(img, x, y).with_src_rect(crop)
+        })
+        .collect()
+}
+
+/// Splits an image into a grid of tiles, the inverse of `grid_concat_images`
+///
+/// Tiles are returned left to right, top to bottom. When the image doesn't
+/// evenly divide, the remainder is spread across the leading rows and columns so
+/// tiles differ in size by at most one pixel. Requesting zero rows or columns
+/// returns no tiles.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to split
+/// * `rows` - Number of rows of tiles
+/// * `cols` - Number of tiles per row
+///
+/// # Returns
+/// * Vec of `rows * cols` ImageBuffers
+///
+/// # Example
+/// ```
+/// use image_concat_rs::split_image;
+/// let img = image::open("./test/1.png").unwrap().into_rgb8();
+/// let tiles = split_image(&img, 2, 3);
+/// assert_eq!(tiles.len(), 6);
+/// ```
+pub fn split_image<P: Pixel + 'static>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    rows: u32,
+    cols: u32,
+) -> Vec<ImageBuffer<P, Vec<P::Subpixel>>> {
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
+
+    // Start and size of each span when splitting `size` into `count` parts
+    let spans = |size: u32, count: u32| {
+        let (base, remainder) = (size / count, size % count);
+        (0..count)
+            .scan(0, move |start, idx| {
+                let len = base + u32::from(idx < remainder);
+                let span = (*start, len);
+                *start += len;
+                Some(span)
+            })
+            .collect::<Vec<_>>()
+    };
+    let xs = spans(img.width(), cols);
+    let ys = spans(img.height(), rows);
+
+    ys.iter()
+        .flat_map(|(y, height)| {
+            xs.iter()
+                .map(move |(x, width)| imageops::crop_imm(img, *x, *y, *width, *height).to_image())
+        })
+        .collect()
+}
+
+/// Trims uniform borders matching a background color from the edges of an image
+///
+/// Rows and columns are removed from each edge for as long as every pixel in
+/// them is within `tolerance` of `bg` on every channel, e.g. the white margins
+/// around a scanned document. Only the edges are trimmed, background colored
+/// regions inside the content are kept. An image that is entirely background
+/// is trimmed to 0x0.
+///
+/// # Arguments
+/// * `img` - ImageBuffer to trim
+/// * `bg` - Border color to remove
+/// * `tolerance` - Largest per channel difference from `bg`, in channel units, still treated as border
+///
+/// # Returns
+/// * `ImageBuffer` - Copy of the content inside the border
+///
+/// # Example
+/// ```
+/// use image_concat_rs::autocrop;
+/// let img = image::open("./test/1.png").unwrap().into_rgb8();
+/// let trimmed = autocrop(&img, image::Rgb([255, 255, 255]), 8.0);
+/// ```
+pub fn autocrop<P: Pixel + 'static>(
+    img: &ImageBuffer<P, Vec<P::Subpixel>>,
+    bg: P,
+    tolerance: f64,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    let is_bg = |pixel: &P| {
+        pixel.channels().iter().zip(bg.channels()).all(|(a, b)| {
+            let a = a.to_f64().unwrap_or(0.0);
+            let b = b.to_f64().unwrap_or(0.0);
+            (a - b).abs() <= tolerance
+        })
+    };
+    let row_is_bg = |y: u32| (0..img.width()).all(|x| is_bg(img.get_pixel(x, y)));
+
+    let Some(top) = (0..img.height()).find(|&y| !row_is_bg(y)) else {
+        return ImageBuffer::new(0, 0);
+    };
+    // A non background row exists, so the bottom search and column searches always find one
+    let bottom = (top..img.height())
+        .rev()
+        .find(|&y| !row_is_bg(y))
+        .unwrap_or(top);
+    let col_is_bg = |x: u32| (top..=bottom).all(|y| is_bg(img.get_pixel(x, y)));
+    let left = (0..img.width()).find(|&x| !col_is_bg(x)).unwrap_or(0);
+    let right = (left..img.width())
+        .rev()
+        .find(|&x| !col_is_bg(x))
+        .unwrap_or(left);
+
+    imageops::crop_imm(img, left, top, right - left + 1, bottom - top + 1).to_image()
+}
+
+/// How an image is fitted into a fixed size grid cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Fit {
+    /// Keep the image's size and center it, padding smaller images and cropping larger ones
+    #[default]
+    Center,
+    /// Scale the image to fill the cell, cropping whatever overflows
+    Cover,
+    /// Scale the image to fit within the cell, padding whatever is left
+    Contain,
+}
+
+/// Concatenates images into a grid of identically sized cells
+///
+/// Each image occupies exactly `cell_width` x `cell_height`, fitted according to
+/// `fit` and centered within its cell, so rows and columns line up perfectly
+/// regardless of the source sizes. Padding is zero filled. `Fit::Cover` and
+/// `Fit::Contain` require a resized copy of every image.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to place in a grid
+/// * `columns` - Number of images per row
+/// * `cell_width` - Width of every cell
+/// * `cell_height` - Height of every cell
+/// * `fit` - Fit::Center, Fit::Cover or Fit::Contain
+/// * `filter` - Sampling filter used when resizing
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{grid_concat_fixed_cells, Fit};
+/// use image::imageops::FilterType;
+/// let imgs: Vec<_> = (1..=5)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = grid_concat_fixed_cells(&imgs, 3, 200, 200, Fit::Cover, FilterType::Triangle);
+/// ```
+pub fn grid_concat_fixed_cells<P: Pixel + 'static>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    cell_width: u32,
+    cell_height: u32,
+    fit: Fit,
+    filter: FilterType,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let rows = images.len().div_ceil(columns);
+    let total = |cells: usize, size: u32| {
+        u32::try_from(cells)
+            .ok()
+            .and_then(|cells| cells.checked_mul(size))
+            .ok_or(ConcatError::DimensionOverflow)
+    };
+    let mut buffer = ImageBuffer::new(
+        total(columns.min(images.len()), cell_width)?,
+        total(rows, cell_height)?,
+    );
+
+    for (idx, img) in images.iter().enumerate() {
+        let (width, height) = img.dimensions();
+        let scale_x = cell_width as f64 / max(width, 1) as f64;
+        let scale_y = cell_height as f64 / max(height, 1) as f64;
+        let scaled = |scale: f64| {
+            let size = |size: u32| max((size as f64 * scale).round() as u32, 1);
+            imageops::resize(img, size(width), size(height), filter)
+        };
+        let fitted = match fit {
+            Fit::Center => None,
+            Fit::Cover => Some(scaled(scale_x.max(scale_y))),
+            Fit::Contain => Some(scaled(scale_x.min(scale_y))),
+        };
+        let fitted = fitted.as_ref().unwrap_or(img);
+
+        // Crop anything overflowing the cell from both sides, then center what's left
+        let (fitted_width, fitted_height) = fitted.dimensions();
+        let (visible_width, visible_height) =
+            (fitted_width.min(cell_width), fitted_height.min(cell_height));
+        let visible = fitted.view(
+            (fitted_width - visible_width) / 2,
+            (fitted_height - visible_height) / 2,
+            visible_width,
+            visible_height,
+        );
+
+        let cell_x = (idx % columns) as u32 * cell_width;
+        let cell_y = (idx / columns) as u32 * cell_height;
+        buffer.copy_from(
+            &*visible,
+            cell_x + (cell_width - visible_width) / 2,
+            cell_y + (cell_height - visible_height) / 2,
+        )?;
+    }
+
+    Ok(buffer)
+}
+
+/// Width of the widest image in each grid column and height of the tallest image in each grid row
+fn grid_cell_sizes(
+    dimensions: impl ExactSizeIterator<Item = (u32, u32)>,
+    columns: usize,
+    fill: FillOrder,
+) -> (Vec<u32>, Vec<u32>) {
+    let rows = dimensions.len().div_ceil(columns);
+    let mut col_widths = vec![0; columns];
+    let mut row_heights = vec![0; rows];
+    for (idx, (width, height)) in dimensions.enumerate() {
+        let (row, col) = fill.cell(idx, columns, rows);
+        col_widths[col] = max(col_widths[col], width);
+        row_heights[row] = max(row_heights[row], height);
+    }
+
+    (col_widths, row_heights)
+}
+
+/// Concatenates images into columns
+///
+/// This will take already loaded images and concatenate them in vertical columns.
+///
+/// Given a desired number of columns, it will divde them as evenly as possible,
+/// placing what will evenly divide into all columns and spreading the remainders
+/// across the front columns.
+///
+/// The order is currently top to bottom, moving to the next column from left to right.
+/// This order might change as it makes knowing where empty rows are a bit unintuitive.
+/// For a grid whose cells line up across columns, filled in either order, see
+/// `grid_concat_images_with_fill` and `FillOrder`.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate in columns
+/// * `columns` - Number of columns to split images into
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{column_concat_images, ConcatDirection};
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = column_concat_images(&[img1,img2], 2);
+///
+/// ```
+pub fn column_concat_images<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    column_concat_images_with_remainder(images, columns, ColumnRemainder::Front)
+}
+
+/// How images are split between columns when they don't divide evenly
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColumnRemainder {
+    /// Split by count, giving the extra images to the front (left) columns
+    #[default]
+    Front,
+    /// Split by count, giving the extra images to the back (right) columns
+    Back,
+    /// Split so the tallest column is as short as possible, keeping images in order.
+    /// Columns may hold differing numbers of images.
+    ByHeight,
+}
+
+/// Concatenates images into columns, choosing where uneven splits go
+///
+/// Same as `column_concat_images`, which always uses `ColumnRemainder::Front`,
+/// but `remainder` picks how images are split between columns. Splitting by
+/// count makes for ragged columns when image heights vary, which
+/// `ColumnRemainder::ByHeight` evens out.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate in columns
+/// * `columns` - Number of columns to split images into
+/// * `remainder` - How images are split between columns
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{column_concat_images_with_remainder, ColumnRemainder};
+/// let imgs: Vec<_> = (1..=7)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = column_concat_images_with_remainder(&imgs, 3, ColumnRemainder::ByHeight);
+/// ```
+pub fn column_concat_images_with_remainder<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+    remainder: ColumnRemainder,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let chunks = match remainder {
+        ColumnRemainder::Front => column_chunks(images.len(), columns),
+        ColumnRemainder::Back => {
+            // Mirror the front split so the longer columns end up on the right
+            let mut chunks = column_chunks(images.len(), columns);
+            let lens: Vec<_> = chunks.iter().rev().map(|chunk| chunk.len()).collect();
+            let mut start = 0;
+            for (chunk, len) in chunks.iter_mut().zip(lens) {
+                *chunk = start..start + len;
+                start += len;
+            }
+            chunks
+        }
+        ColumnRemainder::ByHeight => {
+            let heights: Vec<_> = images.iter().map(|img| img.height() as u64).collect();
+            height_chunks(&heights, columns)
+        }
+    };
+
+    // create blank image the size of the first column
+    let blank_col = ImageBuffer::new(images[0].width(), images[0].height());
+
+    // vec of ImageBlit instructions we will execute all at once after planning the columns
+    let mut blits = Vec::with_capacity(images.len());
+
+    // Build column image blits
+    let mut x = 0;
+    for chunk in chunks {
+        // Add an empty image if more columns than images were requested
+        let col_blits = if chunk.is_empty() {
+            vec![ImageBlit::new(&blank_col, x, 0)]
+        } else {
+            // create a list of ImageBlits to draw a column of images
+            get_concat_blits(&images[chunk], ConcatDirection::Vertical, x, 0)
+        };
+
+        // determine x coord of next column by finding the widest blit
+        let max_width = col_blits
+            .iter()
+            .map(|blit| blit.x.saturating_add(blit.img.width()))
+            .max()
+            .unwrap();
+        // account for current x coord so only current image width is considered
+        let max_width = max_width - x;
+
+        // add blits to blit buffer
+        blits.extend(col_blits);
+
+        // set next column starting x coord
+        x = x.saturating_add(max_width);
+    }
+
+    // execute all blits
+    place_images_in_buffer(&blits)
+}
+
+/// Concatenates images into columns, packing each image into the shortest column
+///
+/// This is the usual masonry column layout. Images are taken in order and each
+/// goes to the bottom of whichever column is currently shortest, leftmost on
+/// ties, which keeps the tallest column and so the output height low. Unlike
+/// `column_concat_images` the images in a column aren't contiguous in `images`,
+/// so reading down a column skips around the input.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate in columns
+/// * `columns` - Number of columns to pack images into
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::column_concat_images_balanced;
+/// let imgs: Vec<_> = (1..=8)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let img_result = column_concat_images_balanced(&imgs, 3);
+/// ```
+pub fn column_concat_images_balanced<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    columns: usize,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if columns == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    // Assign each image to the shortest column so far
+    let mut packed: Vec<Vec<usize>> = vec![Vec::new(); columns];
+    let mut heights = vec![0u64; columns];
+    for (idx, img) in images.iter().enumerate() {
+        let (shortest, _) = heights
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, height)| **height)
+            .unwrap();
+        heights[shortest] += img.height() as u64;
+        packed[shortest].push(idx);
+    }
+
+    // create blank image the size of the first column
+    let blank_col = ImageBuffer::new(images[0].width(), images[0].height());
+
+    let mut blits = Vec::with_capacity(images.len());
+    let mut x: u32 = 0;
+    for column in &packed {
+        if column.is_empty() {
+            // Only happens with more columns than images
+            blits.push(ImageBlit::new(&blank_col, x, 0));
+            x = x.saturating_add(blank_col.width());
+            continue;
+        }
+
+        let mut y: u32 = 0;
+        for &idx in column {
+            blits.push(ImageBlit::new(&images[idx], x, y));
+            y = y.saturating_add(images[idx].height());
+        }
+        x = x.saturating_add(
+            column
+                .iter()
+                .map(|&idx| images[idx].width())
+                .max()
+                .unwrap_or(0),
+        );
+    }
+
+    place_images_in_buffer(&blits)
+}
+
+/// Splits images with `heights` into `columns` runs, in order, minimizing the tallest run
+///
+/// Every column gets at least one image while there are enough to go around,
+/// any extra columns are left empty at the end.
+fn height_chunks(heights: &[u64], columns: usize) -> Vec<std::ops::Range<usize>> {
+    // Number of runs needed if no run may be taller than `limit`
+    let runs_needed = |limit: u64| {
+        let mut runs = 1;
+        let mut height = 0;
+        for &h in heights {
+            if height + h > limit {
+                runs += 1;
+                height = 0;
+            }
+            height += h;
+        }
+        runs
+    };
+
+    // Binary search the smallest limit that fits in `columns` runs
+    let mut low = heights.iter().copied().max().unwrap_or(0);
+    let mut high = heights.iter().sum::<u64>();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if runs_needed(mid) <= columns {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    let mut chunks = Vec::with_capacity(columns);
+    let mut start = 0;
+    for col in 0..columns {
+        // Leave at least one image for each column after this one
+        let reserved = (columns - col - 1).min(heights.len() - start);
+        let mut end = start;
+        let mut height = 0;
+        while end < heights.len() - reserved && (end == start || height + heights[end] <= low) {
+            height += heights[end];
+            end += 1;
+        }
+        chunks.push(start..end);
+        start = end;
+    }
+
+    chunks
+}
+
+/// Suggests a column count for `column_concat_images` giving roughly the target aspect ratio
+///
+/// Every column count from 1 to the number of images is tried, laying images
+/// out the way `column_concat_images` would, and the count whose output
+/// `width / height` is closest to `target_aspect` wins. Closeness is measured as
+/// a ratio, so being twice as wide as the target is as far off as being twice as
+/// tall. Ties go to the fewer columns.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers that will be concatenated
+/// * `target_aspect` - Desired width divided by height, e.g. 1.0 for roughly square
+///
+/// # Returns
+/// * Column count, 1 when there are no images
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{column_concat_images, suggest_columns};
+/// let imgs: Vec<_> = (1..=8)
+///     .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+///     .collect();
+/// let columns = suggest_columns(&imgs, 1.0);
+/// let img_result = column_concat_images(&imgs, columns);
+/// ```
+pub fn suggest_columns<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    target_aspect: f32,
+) -> usize {
+    let target = (target_aspect as f64).ln();
+    let mut best = (1, f64::INFINITY);
+
+    for columns in 1..=images.len() {
+        let (mut width, mut height) = (0u64, 0u64);
+        for chunk in column_chunks(images.len(), columns) {
+            let column = &images[chunk];
+            width += column
+                .iter()
+                .map(|img| img.width() as u64)
+                .max()
+                .unwrap_or(0);
+            height = max(height, column.iter().map(|img| img.height() as u64).sum());
+        }
+        if width == 0 || height == 0 {
+            continue;
+        }
+
+        let distance = ((width as f64 / height as f64).ln() - target).abs();
+        if distance < best.1 {
+            best = (columns, distance);
+        }
+    }
+
+    best.0
+}
+
+/// Index ranges of the images in each column when splitting `len` images into `columns` by count,
+/// with the remainder spread across the front columns
+fn column_chunks(len: usize, columns: usize) -> Vec<std::ops::Range<usize>> {
+    let (chunk_size, chunk_remainder) = (len / columns, len % columns);
+    (0..columns)
+        .scan(0, |start, idx| {
+            let end = *start + chunk_size + usize::from(idx < chunk_remainder);
+            let chunk = *start..end;
+            *start = end;
+            Some(chunk)
+        })
+        .collect()
+}
+
+/// Concatenates images into columns of fixed widths
+///
+/// Images are divided between columns the same way as `column_concat_images`,
+/// with one column per entry in `column_widths`. Instead of each column being as
+/// wide as its widest image, column `n` is exactly `column_widths[n]` pixels wide
+/// so columns line up across layouts. Narrower images are padded on the right and
+/// wider ones are cropped to the column, keeping their left side.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate in columns
+/// * `column_widths` - Width of each column, left to right
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::ColumnCountZero` if no widths are given
+///
+/// # Example
+/// ```
+/// use image_concat_rs::column_concat_images_with_widths;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = column_concat_images_with_widths(&[img1,img2], &[300, 200]);
+/// ```
+pub fn column_concat_images_with_widths<P: Pixel + 'static>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    column_widths: &[u32],
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if column_widths.is_empty() {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let total_width = column_widths
+        .iter()
+        .try_fold(0u32, |total, width| total.checked_add(*width))
+        .ok_or(ConcatError::DimensionOverflow)?;
+    let chunk_size = images.len() / column_widths.len();
+    let chunk_remainder = images.len() % column_widths.len();
+
+    // Crop anything wider than its column so it can't spill into the next one
+    let mut fitted = Vec::with_capacity(images.len());
+    let mut origins = Vec::with_capacity(images.len());
+    let mut total_height = 0;
+    let (mut start, mut x) = (0, 0);
+    for (idx, &column_width) in column_widths.iter().enumerate() {
+        let end = start + chunk_size + usize::from(idx < chunk_remainder);
+        let mut y: u32 = 0;
+        for img in &images[start..end] {
+            let img = if img.width() > column_width {
+                Cow::Owned(imageops::crop_imm(img, 0, 0, column_width, img.height()).to_image())
+            } else {
+                Cow::Borrowed(img)
+            };
+            origins.push((x, y));
+            y = y
+                .checked_add(img.height())
+                .ok_or(ConcatError::DimensionOverflow)?;
+            fitted.push(img);
+        }
+
+        total_height = max(total_height, y);
+        x += column_width;
+        start = end;
+    }
+
+    let blits: Vec<_> = fitted
+        .iter()
+        .zip(origins)
+        .map(|(img, (x, y))| ImageBlit::new(img, x, y))
+        .collect();
+    place_images_in_buffer_clipped(&blits, total_width, total_height)
+}
+
+/// Concatenates images into rows
+///
+/// This will take already loaded images and concatenate them in horizontal rows.
+/// It is the row counterpart of `column_concat_images`.
+///
+/// Given a desired number of rows, it will divde them as evenly as possible,
+/// placing what will evenly divide into all rows and spreading the remainders
+/// across the front rows.
+///
+/// The order is left to right, moving to the next row from top to bottom.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to concatenate in rows
+/// * `rows` - Number of rows to split images into
+///
+/// # Returns
+/// * `Result<ImageBuffer, ConcatError>`
+/// * `ConcatError::ColumnCountZero` if `rows` is 0
+///
+/// # Example
+/// ```
+/// use image_concat_rs::row_concat_images;
+/// let img1 = image::open("./test/1.png").unwrap().into_rgb8();
+/// let img2 = image::open("./test/2.png").unwrap().into_rgb8();
+/// let img_result = row_concat_images(&[img1,img2], 2);
+/// ```
+pub fn row_concat_images<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    rows: usize,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+    if rows == 0 {
+        return Err(ConcatError::ColumnCountZero);
+    }
+
+    let num_images = images.len();
+
+    // Max number of images per row
+    let chunk_size = num_images / rows;
+    // Starting index of rows that will have less images
+    let chunk_remainder = num_images % rows;
+    // create blank image the size of the first row
+    let blank_row = ImageBuffer::new(images[0].width(), images[0].height());
+
+    // vec of ImageBlit instructions we will execute all at once after planning the rows
+    let mut blits = Vec::with_capacity(num_images);
+
+    // Build row image blits
+    let mut start = 0;
+    let mut y = 0;
+    for idx in 0..rows {
+        // Determine if this is a full size row or a partial row
+        let chunk_size = if idx < chunk_remainder {
+            chunk_size + 1
+        } else {
+            chunk_size
+        };
+        let end = start + chunk_size;
+
+        // Add an empty image if more rows than images were requested
+        let row_blits = if start >= num_images {
+            vec![ImageBlit::new(&blank_row, 0, y)]
+        } else {
+            // create a list of ImageBlits to draw a row of images
+            get_concat_blits(&images[start..end], ConcatDirection::Horizontal, 0, y)
+        };
+
+        // determine y coord of next row by finding the tallest blit
+        let max_height = row_blits
+            .iter()
+            .map(|blit| blit.y.saturating_add(blit.img.height()))
+            .max()
+            .unwrap();
+        // account for current y coord so only current image height is considered
+        let max_height = max_height - y;
+
+        // add blits to blit buffer
+        blits.extend(row_blits);
+
+        // set next row starting y coord
+        y = y.saturating_add(max_height);
+
+        // update image index
+        start = end;
+    }
+
+    // execute all blits
+    place_images_in_buffer(&blits)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    /// Unique path in the system temp dir so parallel tests don't clobber each other
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("image-concat-rs-{}-{}", std::process::id(), name))
+    }
+
+    /// Panics at the first pixel of `actual` and `expected` that differ at all
+    fn assert_images_equal<P: image::Pixel<Subpixel = u8>>(
+        actual: &image::ImageBuffer<P, Vec<u8>>,
+        expected: &image::ImageBuffer<P, Vec<u8>>,
+    ) {
+        assert_images_close(actual, expected, 0);
+    }
+
+    /// Panics at the first pixel where a channel of `actual` and `expected` differs
+    /// by more than `tolerance`, e.g. to allow for rounding in resampled output
+    fn assert_images_close<P: image::Pixel<Subpixel = u8>>(
+        actual: &image::ImageBuffer<P, Vec<u8>>,
+        expected: &image::ImageBuffer<P, Vec<u8>>,
+        tolerance: u8,
+    ) {
+        assert_eq!(
+            actual.dimensions(),
+            expected.dimensions(),
+            "image dimensions differ"
+        );
+        let mismatch = actual
+            .enumerate_pixels()
+            .zip(expected.pixels())
+            .find(|((_, _, a), b)| {
+                a.channels()
+                    .iter()
+                    .zip(b.channels())
+                    .any(|(a, b)| a.abs_diff(*b) > tolerance)
+            });
+        if let Some(((x, y, a), b)) = mismatch {
+            panic!(
+                "pixel ({x}, {y}) is {:?}, expected {:?} within {tolerance}",
+                a.channels(),
+                b.channels()
+            );
+        }
+    }
+
+    /// Compares `img` with the reference image `./test/gold/{name}.png`
+    ///
+    /// Run the tests with `UPDATE_GOLD=1` to rewrite the references from the
+    /// current output after an intended change, then check the new images by eye.
+    fn assert_matches_gold(img: &image::RgbImage, name: &str) {
+        let path = PathBuf::from(format!("./test/gold/{name}.png"));
+        if std::env::var_os("UPDATE_GOLD").is_some() {
+            img.save(&path).unwrap();
+            return;
+        }
+
+        let gold = image::open(&path)
+            .unwrap_or_else(|err| panic!("couldn't load {}: {err}", path.display()))
+            .into_rgb8();
+        assert_images_equal(img, &gold);
+    }
+
+    #[test]
+    fn test_concat_images() {
+        let imgs = vec![
+            image::open("./test/1.png").unwrap().into_rgb8(),
+            image::open("./test/2.png").unwrap().into_rgb8(),
+        ];
+        let expected_w = imgs.iter().map(|img| img.width()).max().unwrap();
+        let expected_h: u32 = imgs.iter().map(|img| img.height()).sum();
+
+        let img_result = super::concat_images(&imgs, super::ConcatDirection::Vertical).unwrap();
+        assert_eq!(img_result.width(), expected_w);
+        assert_eq!(img_result.height(), expected_h);
+        assert_matches_gold(&img_result, "vertical");
+
+        let img_result = super::concat_images(&imgs, super::ConcatDirection::Horizontal).unwrap();
+        assert_matches_gold(&img_result, "horizontal");
+    }
+
+    #[test]
+    fn test_gold_layouts() {
+        let imgs: Vec<_> = (1..=8)
+            .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+            .collect();
+
+        // 3, 3 and 2 images per column
+        let img = super::column_concat_images(&imgs, 3).unwrap();
+        assert_matches_gold(&img, "columns");
+
+        // narrower images centered with spacing over a background
+        let narrow: Vec<_> = imgs[..3]
+            .iter()
+            .enumerate()
+            .map(|(i, img)| {
+                image::imageops::crop_imm(img, 0, 0, 422 - 100 * i as u32, 32).to_image()
+            })
+            .collect();
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(6),
+            alignment: super::Alignment::Center,
+            background: Some(image::Rgb([40, 80, 120])),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        let img = super::concat_images_with_options(&narrow, &options).unwrap();
+        assert_matches_gold(&img, "centered_spacing");
+    }
+
+    #[test]
+    fn test_column_concat_images_unbalanced() {
+        let single_img = vec![image::open("./test/1.png").unwrap().into_rgb8()];
+        // request concatting 2 columns, but only pass 1 image
+        let _img_result = super::column_concat_images(&single_img, 2).unwrap();
+    }
+
+    #[test]
+    fn test_load_and_vert_concat_images_truncated() {
+        // write out a copy of a test image with the back half of its data chopped off
+        let bytes = std::fs::read("./test/2.png").unwrap();
+        let truncated = temp_path("truncated.png");
+        std::fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+
+        let paths = [PathBuf::from("./test/1.png"), truncated.clone()];
+        let img_result = super::load_and_vert_concat_images(&paths);
+        std::fs::remove_file(&truncated).unwrap();
+
+        assert!(matches!(img_result, Err(super::ConcatError::Decode(_))));
+    }
+
+    #[test]
+    fn test_load_and_vert_concat_images_missing_file() {
+        let paths = [PathBuf::from("./test/1.png"), temp_path("missing.png")];
+        let img_result = super::load_and_vert_concat_images(&paths);
+
+        match img_result {
+            Err(super::ConcatError::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+                // error should point at the offending file
+                assert!(err.to_string().contains("missing.png"));
+            }
+            _ => panic!("expected an Io error"),
+        }
+    }
+
+    #[test]
+    fn test_load_and_vert_concat_images_mismatched_widths() {
+        let wide = temp_path("wide.png");
+        let narrow = temp_path("narrow.png");
+        image::RgbImage::from_pixel(4, 2, image::Rgb([255, 0, 0]))
+            .save(&wide)
+            .unwrap();
+        image::RgbImage::from_pixel(2, 2, image::Rgb([0, 255, 0]))
+            .save(&narrow)
+            .unwrap();
+
+        let img = super::load_and_vert_concat_images(&[wide.clone(), narrow.clone()]).unwrap();
+        std::fs::remove_file(&wide).unwrap();
+        std::fs::remove_file(&narrow).unwrap();
+
+        assert_eq!(img.dimensions(), (4, 4));
+        assert_eq!(img.get_pixel(3, 1), &image::Rgb([255, 0, 0]));
+        // narrow image rows should start at x=0 of each buffer row
+        assert_eq!(img.get_pixel(0, 2), &image::Rgb([0, 255, 0]));
+        assert_eq!(img.get_pixel(1, 3), &image::Rgb([0, 255, 0]));
+        // remainder of the narrow rows are left as padding
+        assert_eq!(img.get_pixel(2, 2), &image::Rgb([0, 0, 0]));
+        assert_eq!(img.get_pixel(3, 3), &image::Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_concat_images_with_background() {
+        let background = image::Rgb([12, 34, 56]);
+        let imgs = vec![
+            image::RgbImage::from_pixel(2, 2, image::Rgb([255, 0, 0])),
+            image::RgbImage::from_pixel(4, 2, image::Rgb([0, 255, 0])),
+        ];
+
+        let img = super::concat_images_with_background(
+            &imgs,
+            super::ConcatDirection::Vertical,
+            background,
+        )
+        .unwrap();
+
+        assert_eq!(img.dimensions(), (4, 4));
+        // padding to the right of the narrow image
+        assert_eq!(img.get_pixel(2, 0), &background);
+        assert_eq!(img.get_pixel(3, 1), &background);
+        // images themselves are untouched
+        assert_eq!(img.get_pixel(1, 1), &image::Rgb([255, 0, 0]));
+        assert_eq!(img.get_pixel(3, 3), &image::Rgb([0, 255, 0]));
+    }
+
+    #[test]
+    fn test_get_concat_blits_aligned_vertical() {
+        let imgs = vec![image::RgbImage::new(300, 10), image::RgbImage::new(100, 10)];
+        let expected = [
+            (super::Alignment::Start, 0),
+            (super::Alignment::Center, 100),
+            (super::Alignment::End, 200),
+        ];
+
+        for (alignment, expected_x) in expected {
+            let blits = super::get_concat_blits_aligned(
+                &imgs,
+                super::ConcatDirection::Vertical,
+                alignment,
+                0,
+                0,
+            );
+            // widest image always sits at the leading edge
+            assert_eq!((blits[0].x, blits[0].y), (0, 0));
+            assert_eq!((blits[1].x, blits[1].y), (expected_x, 10), "{alignment:?}");
+        }
+    }
+
+    #[test]
+    fn test_get_concat_blits_aligned_horizontal() {
+        let imgs = vec![image::RgbImage::new(10, 30), image::RgbImage::new(10, 11)];
+        let expected = [
+            (super::Alignment::Start, 5),
+            (super::Alignment::Center, 14),
+            (super::Alignment::End, 24),
+        ];
+
+        for (alignment, expected_y) in expected {
+            let blits = super::get_concat_blits_aligned(
+                &imgs,
+                super::ConcatDirection::Horizontal,
+                alignment,
+                20,
+                5,
+            );
+            assert_eq!((blits[0].x, blits[0].y), (20, 5));
+            assert_eq!((blits[1].x, blits[1].y), (30, expected_y), "{alignment:?}");
+        }
+    }
+
+    #[test]
+    fn test_grid_concat_images() {
+        // vary sizes so every row and column ends up a different size
+        let imgs = vec![
+            image::RgbImage::new(10, 5),
+            image::RgbImage::new(20, 8),
+            image::RgbImage::new(15, 12),
+            image::RgbImage::new(5, 3),
+            image::RgbImage::new(12, 7),
+        ];
+
+        let blits = super::get_grid_blits(&imgs, 2, 0, 0);
+        let origins: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+        // column widths are 15 and 20, row heights are 8, 12 and 7
+        assert_eq!(origins, vec![(0, 0), (15, 0), (0, 8), (15, 8), (0, 20)]);
+
+        let img = super::grid_concat_images(&imgs, 2).unwrap();
+        assert_eq!(img.dimensions(), (35, 27));
+    }
+
+    #[test]
+    fn test_column_concat_images_keeps_final_column() {
+        // 6 images evenly split into 3 columns, each a distinct color
+        let imgs: Vec<_> = (0..6)
+            .map(|i| image::RgbImage::from_pixel(4, 3, image::Rgb([i * 40 + 10, 0, 0])))
+            .collect();
+
+        let img = super::column_concat_images(&imgs, 3).unwrap();
+        assert_eq!(img.dimensions(), (12, 6));
+
+        // images fill top to bottom then left to right
+        for (idx, src) in imgs.iter().enumerate() {
+            let (x, y) = ((idx / 2) as u32 * 4, (idx % 2) as u32 * 3);
+            assert_eq!(img.get_pixel(x, y), src.get_pixel(0, 0), "image {idx}");
+        }
+    }
+
+    #[test]
+    fn test_concat_images_with_spacing() {
+        let background = image::Rgb([200, 100, 0]);
+        let imgs = vec![
+            image::RgbImage::from_pixel(20, 50, image::Rgb([255, 255, 255])),
+            image::RgbImage::from_pixel(20, 50, image::Rgb([255, 255, 255])),
+        ];
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(10),
+            background: Some(background),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+
+        let blits = super::get_concat_blits_with_options(&imgs, &options, 0, 0);
+        assert_eq!(blits[1].y, 60);
+
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.dimensions(), (20, 110));
+        // gap is filled with the background
+        assert_eq!(img.get_pixel(5, 50), &background);
+        assert_eq!(img.get_pixel(5, 59), &background);
+        assert_eq!(img.get_pixel(5, 60), &image::Rgb([255, 255, 255]));
+    }
+
+    #[test]
+    fn test_row_concat_images_unbalanced() {
+        // 7 images into 3 rows should give rows of 3, 2 and 2
+        let imgs: Vec<_> = (0..7)
+            .map(|i| image::RgbImage::from_pixel(4, 3, image::Rgb([i * 30 + 10, 0, 0])))
+            .collect();
+
+        let img = super::row_concat_images(&imgs, 3).unwrap();
+        assert_eq!(img.dimensions(), (12, 9));
+
+        // (row, column) each image should land in
+        let expected = [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (2, 0), (2, 1)];
+        for (src, (row, col)) in imgs.iter().zip(expected) {
+            assert_eq!(img.get_pixel(col * 4, row * 3), src.get_pixel(0, 0));
+        }
+        // short rows are left empty at the end
+        assert_eq!(img.get_pixel(8, 3), &image::Rgb([0, 0, 0]));
+        assert_eq!(img.get_pixel(8, 6), &image::Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_load_and_vert_concat_images_as_rgba() {
+        let first = temp_path("rgba_first.png");
+        let second = temp_path("rgba_second.png");
+        image::RgbaImage::from_pixel(3, 2, image::Rgba([255, 0, 0, 64]))
+            .save(&first)
+            .unwrap();
+        image::RgbaImage::from_pixel(3, 2, image::Rgba([0, 0, 255, 0]))
+            .save(&second)
+            .unwrap();
+
+        let paths = [first.clone(), second.clone()];
+        let img = super::load_and_vert_concat_images_as::<image::Rgba<u8>>(&paths).unwrap();
+        // rgb loading must refuse rgba sources rather than misinterpret their bytes
+        let rgb_result = super::load_and_vert_concat_images(&paths);
+        std::fs::remove_file(&first).unwrap();
+        std::fs::remove_file(&second).unwrap();
+
+        assert_eq!(img.dimensions(), (3, 4));
+        assert_eq!(img.get_pixel(2, 1), &image::Rgba([255, 0, 0, 64]));
+        assert_eq!(img.get_pixel(0, 3), &image::Rgba([0, 0, 255, 0]));
+        assert!(rgb_result.is_err());
+    }
+
+    #[test]
+    fn test_concat_builder() {
+        let background = image::Rgb([255, 255, 255]);
+        let img = super::ConcatBuilder::new()
+            .spacing(8)
+            .alignment(super::Alignment::Center)
+            .background(background)
+            .add_image(image::RgbImage::from_pixel(10, 4, image::Rgb([255, 0, 0])))
+            .add_image(image::RgbImage::from_pixel(4, 4, image::Rgb([0, 0, 255])))
+            .add_path(PathBuf::from("./test/1.png"))
+            .build()
+            .unwrap();
+
+        assert_eq!(img.dimensions(), (422, 4 + 8 + 4 + 8 + 32));
+        // narrow images are centered over the background
+        assert_eq!(img.get_pixel(205, 0), &background);
+        assert_eq!(img.get_pixel(206, 0), &image::Rgb([255, 0, 0]));
+        assert_eq!(img.get_pixel(209, 12), &image::Rgb([0, 0, 255]));
+        // spacing between images
+        assert_eq!(img.get_pixel(206, 4), &background);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_load_and_vert_concat_images_parallel() {
+        let paths: Vec<_> = (1..=8)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+
+        let serial = super::load_and_vert_concat_images(&paths).unwrap();
+        let parallel = super::load_and_vert_concat_images_parallel(&paths).unwrap();
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_place_images_in_buffer_parallel() {
+        let imgs: Vec<_> = (1..=4)
+            .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+            .collect();
+        let mut blits = super::get_concat_blits(&imgs, super::ConcatDirection::Horizontal, 0, 0);
+        // overlapping, anchored and transformed blits on top of the plain concat
+        blits.push(
+            super::ImageBlit::new(&imgs[0], 30, 40).with_transform(super::Transform::Rotate90),
+        );
+        blits.push(super::ImageBlit::new(&imgs[1], 60, 60).with_origin(super::Anchor::Center));
+
+        let serial = super::place_images_in_buffer(&blits).unwrap();
+        let parallel = super::place_images_in_buffer_parallel(&blits).unwrap();
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_anchor() {
+        let img = image::RgbImage::from_pixel(50, 50, image::Rgb([255, 0, 0]));
+
+        let blit = super::ImageBlit::new(&img, 100, 100).with_origin(super::Anchor::Center);
+        let buffer = super::place_images_in_buffer(&[blit]).unwrap();
+        // centered at (100,100) puts the top left at (75,75)
+        assert_eq!(buffer.dimensions(), (125, 125));
+        assert_eq!(buffer.get_pixel(74, 74), &image::Rgb([0, 0, 0]));
+        assert_eq!(buffer.get_pixel(75, 75), &image::Rgb([255, 0, 0]));
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_anchor_grows_buffer() {
+        let red = image::RgbImage::from_pixel(50, 50, image::Rgb([255, 0, 0]));
+        let blue = image::RgbImage::from_pixel(10, 10, image::Rgb([0, 0, 255]));
+
+        // centering at (10,10) would put the top left at (-15,-15)
+        let blits = [
+            super::ImageBlit::new(&red, 10, 10).with_origin(super::Anchor::Center),
+            super::ImageBlit::new(&blue, 0, 0),
+        ];
+        let buffer = super::place_images_in_buffer(&blits).unwrap();
+
+        // everything is shifted by 15 to fit the anchored image
+        assert_eq!(buffer.dimensions(), (50, 50));
+        assert_eq!(buffer.get_pixel(0, 0), &image::Rgb([255, 0, 0]));
+        assert_eq!(buffer.get_pixel(15, 15), &image::Rgb([0, 0, 255]));
+        assert_eq!(buffer.get_pixel(24, 24), &image::Rgb([0, 0, 255]));
+        assert_eq!(buffer.get_pixel(25, 25), &image::Rgb([255, 0, 0]));
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_clipped() {
+        // distinct color per quadrant to check which part of the image survives
+        let img = image::RgbImage::from_fn(10, 10, |x, y| image::Rgb([x as u8, y as u8, 255]));
+
+        let blits = [
+            // hangs 4px off the right and 2px off the bottom
+            super::ImageBlit::new(&img, 14, 12),
+            // centered on the top left corner, so only the bottom right quarter is visible
+            super::ImageBlit::new(&img, 0, 0).with_origin(super::Anchor::Center),
+            // entirely out of bounds
+            super::ImageBlit::new(&img, 30, 30),
+        ];
+        let buffer = super::place_images_in_buffer_clipped(&blits, 20, 20).unwrap();
+        assert_eq!(buffer.dimensions(), (20, 20));
+
+        assert_eq!(buffer.get_pixel(13, 12), &image::Rgb([0, 0, 0]));
+        assert_eq!(buffer.get_pixel(14, 12), &image::Rgb([0, 0, 255]));
+        assert_eq!(buffer.get_pixel(19, 19), &image::Rgb([5, 7, 255]));
+
+        assert_eq!(buffer.get_pixel(0, 0), &image::Rgb([5, 5, 255]));
+        assert_eq!(buffer.get_pixel(4, 4), &image::Rgb([9, 9, 255]));
+        assert_eq!(buffer.get_pixel(5, 5), &image::Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_concat_images_scaled() {
+        let imgs = vec![
+            image::RgbImage::from_pixel(20, 10, image::Rgb([255, 0, 0])),
+            image::RgbImage::from_pixel(45, 60, image::Rgb([0, 255, 0])),
+        ];
+
+        let img = super::concat_images_scaled(
+            &imgs,
+            super::ConcatDirection::Vertical,
+            30,
+            image::imageops::FilterType::Nearest,
+        )
+        .unwrap();
+
+        // 20x10 -> 30x15 and 45x60 -> 30x40
+        assert_eq!(img.dimensions(), (30, 55));
+        // both images span the full width, so there's no padding on any row
+        for y in 0..img.height() {
+            assert_ne!(img.get_pixel(29, y), &image::Rgb([0, 0, 0]), "row {y}");
+        }
+        assert_eq!(img.get_pixel(29, 14), &image::Rgb([255, 0, 0]));
+        assert_eq!(img.get_pixel(29, 15), &image::Rgb([0, 255, 0]));
+
+        // zero sized images are skipped rather than scaled to an infinite size
+        let with_empty = [image::RgbImage::new(0, 10), imgs[0].clone()];
+        let img = super::concat_images_scaled(
+            &with_empty,
+            super::ConcatDirection::Vertical,
+            30,
+            image::imageops::FilterType::Nearest,
+        )
+        .unwrap();
+        assert_eq!(img.dimensions(), (30, 15));
+
+        let only_empty = [image::RgbImage::new(10, 0)];
+        assert!(matches!(
+            super::concat_images_scaled(
+                &only_empty,
+                super::ConcatDirection::Horizontal,
+                30,
+                image::imageops::FilterType::Nearest,
+            ),
+            Err(super::ConcatError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_concat_images_wrapped() {
+        let imgs = vec![
+            image::RgbImage::new(400, 20),
+            image::RgbImage::new(400, 30),
+            image::RgbImage::new(400, 25),
+        ];
+
+        let blits = super::get_wrapped_blits(&imgs, 900, 0, 0);
+        let origins: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+        assert_eq!(origins, vec![(0, 0), (400, 0), (0, 30)]);
+
+        let img = super::concat_images_wrapped(&imgs, 900).unwrap();
+        assert_eq!(img.dimensions(), (800, 55));
+    }
+
+    #[test]
+    fn test_concat_dimensions() {
+        let imgs = vec![
+            image::RgbImage::new(10, 20),
+            image::RgbImage::new(30, 5),
+            image::RgbImage::new(15, 40),
+        ];
+
+        for direction in [
+            super::ConcatDirection::Vertical,
+            super::ConcatDirection::Horizontal,
+        ] {
+            let img = super::concat_images(&imgs, direction).unwrap();
+            assert_eq!(
+                super::concat_dimensions(&imgs, direction).unwrap(),
+                img.dimensions()
+            );
+        }
+
+        let blits = super::get_grid_blits(&imgs, 2, 3, 4);
+        let img = super::place_images_in_buffer(&blits).unwrap();
+        assert_eq!(super::blits_dimensions(&blits).unwrap(), img.dimensions());
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_overflow() {
+        let img = image::RgbImage::new(10, 10);
+
+        let blits = [super::ImageBlit::new(&img, u32::MAX - 5, 0)];
+        assert!(matches!(
+            super::place_images_in_buffer(&blits),
+            Err(super::ConcatError::DimensionOverflow)
+        ));
+        assert!(matches!(
+            super::blits_dimensions(&blits),
+            Err(super::ConcatError::DimensionOverflow)
+        ));
+
+        // a plan whose cursor runs past u32::MAX
+        let blits = super::get_concat_blits(
+            std::slice::from_ref(&img),
+            super::ConcatDirection::Vertical,
+            0,
+            u32::MAX - 5,
+        );
+        assert!(matches!(
+            super::place_images_in_buffer(&blits),
+            Err(super::ConcatError::DimensionOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_empty_images() {
+        let no_paths: [PathBuf; 0] = [];
+        assert!(matches!(
+            super::load_and_vert_concat_images(&no_paths),
+            Err(super::ConcatError::Empty)
+        ));
+        assert!(matches!(
+            super::load_and_column_concat_images(&no_paths, 2),
+            Err(super::ConcatError::Empty)
+        ));
+
+        let no_imgs: [image::RgbImage; 0] = [];
+        assert!(matches!(
+            super::concat_images(&no_imgs, super::ConcatDirection::Vertical),
+            Err(super::ConcatError::Empty)
+        ));
+        assert!(matches!(
+            super::column_concat_images(&no_imgs, 2),
+            Err(super::ConcatError::Empty)
+        ));
+        assert!(matches!(
+            super::grid_concat_images(&no_imgs, 2),
+            Err(super::ConcatError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_load_and_column_concat_images_extra_columns() {
+        // more columns than images shouldn't trip the empty check for the unused columns
+        let paths = [PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")];
+        let img = super::load_and_column_concat_images(&paths, 3).unwrap();
+        assert_eq!(img.dimensions(), (422 * 2, 32));
+    }
+
+    #[test]
+    fn test_zero_columns() {
+        let imgs = vec![image::RgbImage::new(4, 4), image::RgbImage::new(4, 4)];
+        assert!(matches!(
+            super::column_concat_images(&imgs, 0),
+            Err(super::ConcatError::ColumnCountZero)
+        ));
+        assert!(matches!(
+            super::grid_concat_images(&imgs, 0),
+            Err(super::ConcatError::ColumnCountZero)
+        ));
+        assert!(matches!(
+            super::row_concat_images(&imgs, 0),
+            Err(super::ConcatError::ColumnCountZero)
+        ));
+
+        let paths = [PathBuf::from("./test/1.png")];
+        assert!(matches!(
+            super::load_and_column_concat_images(&paths, 0),
+            Err(super::ConcatError::ColumnCountZero)
+        ));
+    }
+
+    #[test]
+    fn test_stream_vert_concat_to_png() {
+        let narrow = temp_path("stream_narrow.png");
+        image::RgbImage::from_fn(100, 7, |x, y| image::Rgb([x as u8, y as u8, 9]))
+            .save(&narrow)
+            .unwrap();
+        let paths = [
+            PathBuf::from("./test/1.png"),
+            narrow.clone(),
+            PathBuf::from("./test/2.png"),
+        ];
+        let out_path = temp_path("streamed.png");
+
+        super::stream_vert_concat_to_png(&paths, &out_path).unwrap();
+        let streamed = image::open(&out_path).unwrap().into_rgb8();
+        let expected = super::load_and_vert_concat_images(&paths).unwrap();
+        std::fs::remove_file(&narrow).unwrap();
+        std::fs::remove_file(&out_path).unwrap();
+
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let paths: Vec<_> = (1..=4)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+
+        let mut calls = Vec::new();
+        let img = super::load_and_vert_concat_images_with_progress(&paths, |completed, total| {
+            calls.push((completed, total))
+        })
+        .unwrap();
+
+        assert_eq!(img, super::load_and_vert_concat_images(&paths).unwrap());
+        assert_eq!(calls, vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+    }
+
+    #[test]
+    fn test_concat_from_bytes() {
+        let paths: Vec<_> = (1..=3)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+        let buffers: Vec<_> = paths
+            .iter()
+            .map(|path| std::fs::read(path).unwrap())
+            .collect();
+
+        let img =
+            super::concat_images_from_bytes(&buffers, super::ConcatDirection::Vertical).unwrap();
+        assert_eq!(img, super::load_and_vert_concat_images(&paths).unwrap());
+
+        let garbage = vec![vec![0u8; 16]];
+        assert!(
+            super::concat_images_from_bytes(&garbage, super::ConcatDirection::Vertical).is_err()
+        );
+    }
+
+    #[test]
+    fn test_save_with_metadata() {
+        let dims = |xppu| png::PixelDimensions {
+            xppu,
+            yppu: xppu,
+            unit: png::Unit::Meter,
+        };
+        let write_png = |name, pixel_dims| {
+            let path = temp_path(name);
+            let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+            let mut encoder = png::Encoder::new(file, 2, 2);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_pixel_dims(pixel_dims);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[0; 12]).unwrap();
+            writer.finish().unwrap();
+            path
+        };
+        let read_dims = |path: &PathBuf| {
+            let file = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+            png::Decoder::new(file)
+                .read_info()
+                .unwrap()
+                .info()
+                .pixel_dims
+        };
+
+        // 300 DPI is ~11811 pixels per meter
+        let none = write_png("metadata_none.png", None);
+        let dpi_300 = write_png("metadata_300.png", Some(dims(11811)));
+        let dpi_72 = write_png("metadata_72.png", Some(dims(2835)));
+        let out = temp_path("metadata_out.png");
+
+        let sources = [none.clone(), dpi_300.clone(), dpi_72.clone()];
+        let img = super::load_and_vert_concat_images(&sources).unwrap();
+
+        // first source with a DPI wins
+        super::save_with_metadata(&img, &sources, &out, false).unwrap();
+        assert_eq!(
+            read_dims(&out).map(|d| (d.xppu, d.yppu, d.unit)),
+            Some((11811, 11811, png::Unit::Meter))
+        );
+        assert_eq!(image::open(&out).unwrap().into_rgb8(), img);
+
+        // unless strict
+        assert!(matches!(
+            super::save_with_metadata(&img, &sources, &out, true),
+            Err(super::ConcatError::ConflictingDpi)
+        ));
+        super::save_with_metadata(&img, &sources[..2], &out, true).unwrap();
+        assert_eq!(
+            read_dims(&out).map(|d| (d.xppu, d.yppu, d.unit)),
+            Some((11811, 11811, png::Unit::Meter))
+        );
+
+        // no DPI to carry over
+        super::save_with_metadata(&img, std::slice::from_ref(&none), &out, true).unwrap();
+        assert!(read_dims(&out).is_none());
+
+        for path in [none, dpi_300, dpi_72, out] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_border() {
+        use image::Rgb;
+        let red = image::RgbImage::from_pixel(2, 2, Rgb([255, 0, 0]));
+        let blue = image::RgbImage::from_pixel(2, 2, Rgb([0, 0, 255]));
+        let green = Rgb([0, 255, 0]);
+        let border = super::Border {
+            width: 1,
+            color: green,
+        };
+
+        // gap of one border width so neighbours share a line
+        let blits = [
+            super::ImageBlit::new(&red, 0, 0),
+            super::ImageBlit::new(&blue, 3, 0),
+        ];
+
+        let img = super::place_images_in_buffer_with_border(&blits, border, false).unwrap();
+        assert_eq!(img.dimensions(), (7, 4));
+        for (x, y, pixel) in img.enumerate_pixels() {
+            let expected = match (x, y) {
+                (1..=2, 1..=2) => Rgb([255, 0, 0]),
+                (4..=5, 1..=2) => Rgb([0, 0, 255]),
+                _ => green,
+            };
+            assert_eq!(*pixel, expected, "pixel at ({x}, {y})");
+        }
+
+        // canvas frame wraps everything in another border width
+        let img = super::place_images_in_buffer_with_border(&blits, border, true).unwrap();
+        assert_eq!(img.dimensions(), (9, 6));
+        for (x, y, pixel) in img.enumerate_pixels() {
+            let expected = match (x, y) {
+                (2..=3, 2..=3) => Rgb([255, 0, 0]),
+                (5..=6, 2..=3) => Rgb([0, 0, 255]),
+                _ => green,
+            };
+            assert_eq!(*pixel, expected, "pixel at ({x}, {y})");
+        }
+
+        // wider gaps leave the background between frames
+        let blits = [
+            super::ImageBlit::new(&red, 0, 0),
+            super::ImageBlit::new(&blue, 5, 0),
+        ];
+        let img = super::place_images_in_buffer_with_border(&blits, border, false).unwrap();
+        assert_eq!(img.dimensions(), (9, 4));
+        assert_eq!(*img.get_pixel(3, 1), green);
+        assert_eq!(*img.get_pixel(4, 1), Rgb([0, 0, 0]));
+        assert_eq!(*img.get_pixel(5, 1), green);
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn test_label_concat() {
+        use super::ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+        use image::Rgb;
+
+        let font_data = std::fs::read("./test/DejaVuSansMono.ttf").unwrap();
+        let font = FontRef::try_from_slice(&font_data).unwrap();
+        let style = super::CaptionStyle {
+            size: 12.0,
+            color: Rgb([0, 0, 0]),
+            background: Rgb([255, 255, 255]),
+        };
+        let strip_height = font.as_scaled(PxScale::from(12.0)).height().ceil() as u32;
+
+        let gray = image::RgbImage::from_pixel(40, 20, Rgb([128, 128, 128]));
+        let labeled = vec![
+            (gray.clone(), "ab".to_string()),
+            (gray.clone(), String::new()),
+            (gray.clone(), "c".to_string()),
+        ];
+
+        let img = super::label_concat_images(&labeled, 2, &font, &style).unwrap();
+        assert_eq!(img.dimensions(), (80, 2 * (20 + strip_height)));
+
+        // images are untouched and the missing final cell is background
+        assert_eq!(*img.get_pixel(0, 0), Rgb([128, 128, 128]));
+        assert_eq!(
+            *img.get_pixel(79, 20 + strip_height + 19),
+            Rgb([255, 255, 255])
+        );
+
+        // some text is drawn in labeled strips, short enough to leave the rest of the strip as background
+        let background = Rgb([255, 255, 255]);
+        let is_text = |x: u32, y: u32| *img.get_pixel(x, y) != background;
+        let strip_text = |left: u32, top: u32, width: u32| {
+            (left..left + width)
+                .flat_map(|x| (top..top + strip_height).map(move |y| (x, y)))
+                .filter(|(x, y)| is_text(*x, *y))
+                .count()
+        };
+        assert!(strip_text(0, 20, 20) > 0);
+        assert_eq!(strip_text(20, 20, 20), 0);
+        assert!(strip_text(0, 40 + strip_height, 40) > 0);
+
+        // an empty label leaves the whole strip as background
+        assert_eq!(strip_text(40, 20, 40), 0);
+    }
+
+    #[test]
+    fn test_cascade() {
+        let imgs: Vec<_> = (0..3).map(|_| image::RgbImage::new(50, 30)).collect();
+
+        let blits = super::get_cascade_blits(&imgs, 20, 20, 0, 0);
+        let origins: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+        assert_eq!(origins, vec![(0, 0), (20, 20), (40, 40)]);
+
+        let img = super::cascade_concat_images(&imgs, 20, 20).unwrap();
+        assert_eq!(img.dimensions(), (90, 70));
+
+        // negative offsets start from the opposite edge
+        let img = super::cascade_concat_images(&imgs, -20, 10).unwrap();
+        assert_eq!(img.dimensions(), (90, 50));
+        let blits = super::get_cascade_blits(&imgs, -20, 10, 40, 0);
+        let origins: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+        assert_eq!(origins, vec![(40, 0), (20, 10), (0, 20)]);
+    }
+
+    #[test]
+    fn test_masonry() {
+        use image::Rgb;
+        let imgs: Vec<_> = [(400, 300), (300, 200), (500, 250), (320, 240), (640, 360)]
+            .iter()
+            .enumerate()
+            .map(|(idx, (width, height))| {
+                image::RgbImage::from_pixel(*width, *height, Rgb([50 * (idx as u8 + 1), 0, 0]))
+            })
+            .collect();
+        let color = |idx: u8| Rgb([50 * (idx + 1), 0, 0]);
+
+        let img =
+            super::masonry_concat_images(&imgs, 100, 300, image::imageops::FilterType::Nearest)
+                .unwrap();
+        assert_eq!(img.width(), 300);
+
+        // at 100px tall the images are ~133, 150, 200, 133 and 178 wide, so the first row
+        // stretches two images and the second shrinks two, leaving the last on its own
+        assert_eq!(img.height(), 106 + 90 + 100);
+
+        // full rows end flush with max_width
+        assert_eq!(*img.get_pixel(0, 0), color(0));
+        assert_eq!(*img.get_pixel(299, 0), color(1));
+        assert_eq!(*img.get_pixel(0, 106), color(2));
+        assert_eq!(*img.get_pixel(299, 106), color(3));
+
+        // the final row keeps its natural width
+        assert_eq!(*img.get_pixel(177, 196), color(4));
+        assert_eq!(*img.get_pixel(178, 196), Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_concat_dynamic_images() {
+        use image::{DynamicImage, Rgb, Rgba};
+        let rgb = DynamicImage::ImageRgb8(image::RgbImage::from_pixel(2, 1, Rgb([10, 20, 30])));
+        let rgba =
+            DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(2, 1, Rgba([1, 2, 3, 128])));
+
+        let img =
+            super::concat_dynamic_images(&[rgb.clone(), rgba], super::ConcatDirection::Vertical)
+                .unwrap();
+        let DynamicImage::ImageRgba8(img) = img else {
+            panic!("expected RGBA8 output, got {:?}", img.color());
+        };
+        assert_eq!(*img.get_pixel(0, 0), Rgba([10, 20, 30, 255]));
+        assert_eq!(*img.get_pixel(1, 1), Rgba([1, 2, 3, 128]));
+
+        // deeper channels win even without alpha
+        let luma16 = DynamicImage::ImageLuma16(image::ImageBuffer::from_pixel(
+            2,
+            1,
+            image::Luma([u16::MAX]),
+        ));
+        let img = super::concat_dynamic_images(&[rgb, luma16], super::ConcatDirection::Horizontal)
+            .unwrap();
+        assert_eq!(img.color(), image::ColorType::Rgb16);
+        assert_eq!((img.width(), img.height()), (4, 1));
+    }
+
+    #[test]
+    fn test_pattern_background() {
+        use image::Rgb;
+        let (black, white) = (Rgb([0, 0, 0]), Rgb([255, 255, 255]));
+        let pattern =
+            image::RgbImage::from_fn(2, 2, |x, y| if (x + y) % 2 == 0 { white } else { black });
+        let red = image::RgbImage::from_pixel(2, 2, Rgb([255, 0, 0]));
+        let tall = image::RgbImage::from_pixel(5, 3, Rgb([0, 0, 255]));
+
+        let blits = [
+            super::ImageBlit::new(&red, 0, 0),
+            super::ImageBlit::new(&tall, 0, 2),
+        ];
+        let img = super::place_images_in_buffer_with_pattern(&blits, &pattern).unwrap();
+        assert_eq!(img.dimensions(), (5, 5));
+
+        // padding to the right of the narrow image repeats the pattern from the canvas origin
+        assert_eq!(*img.get_pixel(2, 0), white);
+        assert_eq!(*img.get_pixel(3, 0), black);
+        assert_eq!(*img.get_pixel(4, 0), white);
+        assert_eq!(*img.get_pixel(2, 1), black);
+        assert_eq!(*img.get_pixel(3, 1), white);
+        assert_eq!(*img.get_pixel(4, 1), black);
+
+        // images still cover the pattern
+        assert_eq!(*img.get_pixel(1, 1), Rgb([255, 0, 0]));
+        assert_eq!(*img.get_pixel(4, 4), Rgb([0, 0, 255]));
+    }
+
+    #[test]
+    fn test_concat_blit_iter() {
+        let imgs: Vec<_> = [(10, 20), (30, 5), (15, 15)]
+            .iter()
+            .map(|(width, height)| image::RgbImage::new(*width, *height))
+            .collect();
+        let coords = |blits: Vec<super::ImageBlit<image::Rgb<u8>>>| {
+            blits
+                .iter()
+                .map(|blit| (blit.img as *const _, blit.x, blit.y))
+                .collect::<Vec<_>>()
+        };
+
+        for direction in [
+            super::ConcatDirection::Vertical,
+            super::ConcatDirection::Horizontal,
+        ] {
+            let lazy = super::concat_blit_iter(&imgs, direction, 3, 7).collect();
+            let eager = super::get_concat_blits(&imgs, direction, 3, 7);
+            assert_eq!(coords(lazy), coords(eager));
+        }
+    }
+
+    #[test]
+    fn test_reverse_order() {
+        use image::Rgb;
+        let red = image::RgbImage::from_pixel(10, 10, Rgb([255, 0, 0]));
+        let blue = image::RgbImage::from_pixel(20, 20, Rgb([0, 0, 255]));
+        let imgs = [red, blue];
+
+        let options = super::ConcatOptions {
+            order: super::Order::Reverse,
+            spacing: super::Spacing::Fixed(2),
+            ..super::ConcatOptions::new(super::ConcatDirection::Horizontal)
+        };
+        let blits = super::get_concat_blits_with_options(&imgs, &options, 5, 0);
+        let coords: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+        assert_eq!(coords, vec![(27, 0), (5, 0)]);
+
+        // first image ends up on the right
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.dimensions(), (32, 20));
+        assert_eq!(*img.get_pixel(31, 0), Rgb([255, 0, 0]));
+        assert_eq!(*img.get_pixel(0, 0), Rgb([0, 0, 255]));
+
+        // and on the bottom
+        let options = super::ConcatOptions {
+            order: super::Order::Reverse,
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.dimensions(), (20, 30));
+        assert_eq!(*img.get_pixel(0, 29), Rgb([255, 0, 0]));
+        assert_eq!(*img.get_pixel(0, 0), Rgb([0, 0, 255]));
+
+        // A start offset near the edge of u32 saturates instead of overflowing
+        let blits = super::get_concat_blits_with_options(&imgs, &options, 0, u32::MAX - 5);
+        assert!(blits.iter().all(|blit| blit.y >= u32::MAX - 5));
+    }
+
+    #[test]
+    fn test_fixed_cells() {
+        use super::Fit;
+        use image::imageops::FilterType;
+        use image::{GenericImageView, Rgb};
+        let (black, red, blue) = (Rgb([0, 0, 0]), Rgb([255, 0, 0]), Rgb([0, 0, 255]));
+        let small = image::RgbImage::from_pixel(2, 2, red);
+        // left half red, right half blue
+        let wide = image::RgbImage::from_fn(8, 4, |x, _| if x < 4 { red } else { blue });
+
+        // center pads small images and crops large ones around their middle
+        let img = super::grid_concat_fixed_cells(
+            &[small.clone(), wide.clone()],
+            2,
+            4,
+            4,
+            Fit::Center,
+            FilterType::Nearest,
+        )
+        .unwrap();
+        assert_eq!(img.dimensions(), (8, 4));
+        assert_eq!(*img.get_pixel(0, 0), black);
+        assert_eq!(*img.get_pixel(1, 1), red);
+        assert_eq!(*img.get_pixel(2, 2), red);
+        assert_eq!(*img.get_pixel(3, 3), black);
+        assert_eq!(*img.get_pixel(4, 0), red);
+        assert_eq!(*img.get_pixel(5, 3), red);
+        assert_eq!(*img.get_pixel(6, 0), blue);
+        assert_eq!(*img.get_pixel(7, 3), blue);
+
+        // cover scales the small image up to fill and crops the wide one to its center
+        let img = super::grid_concat_fixed_cells(
+            &[small.clone(), wide.clone()],
+            1,
+            4,
+            4,
+            Fit::Cover,
+            FilterType::Nearest,
+        )
+        .unwrap();
+        assert_eq!(img.dimensions(), (4, 8));
+        assert!(img.view(0, 0, 4, 4).pixels().all(|(_, _, p)| p == red));
+        assert_eq!(*img.get_pixel(1, 4), red);
+        assert_eq!(*img.get_pixel(2, 7), blue);
+
+        // contain scales the wide image down to 4x2 and centers it vertically
+        let img =
+            super::grid_concat_fixed_cells(&[wide], 1, 4, 4, Fit::Contain, FilterType::Nearest)
+                .unwrap();
+        assert_eq!(img.dimensions(), (4, 4));
+        assert_eq!(*img.get_pixel(0, 0), black);
+        assert_eq!(*img.get_pixel(0, 1), red);
+        assert_eq!(*img.get_pixel(3, 2), blue);
+        assert_eq!(*img.get_pixel(3, 3), black);
+    }
+
+    #[test]
+    fn test_thumbnail_concat() {
+        let wide = temp_path("thumb_wide.png");
+        let tall = temp_path("thumb_tall.png");
+        let small = temp_path("thumb_small.png");
+        image::RgbImage::new(300, 100).save(&wide).unwrap();
+        image::RgbImage::new(100, 250).save(&tall).unwrap();
+        image::RgbImage::new(20, 30).save(&small).unwrap();
+
+        let paths = [wide, tall, small];
+        let img = super::thumbnail_concat_images(&paths, 50, 3).unwrap();
+
+        // 50x17, 20x50 and the untouched 20x30 side by side
+        assert_eq!(img.dimensions(), (90, 50));
+
+        let real: Vec<_> = (1..=4)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+        let img = super::thumbnail_concat_images(&real, 64, 1).unwrap();
+        assert!(img.width() <= 64);
+        assert!(img.height() <= 64 * 4);
+
+        // Missing files are reported with their path like every other loader
+        let missing = [PathBuf::from("./test/missing.png")];
+        let err = super::thumbnail_concat_images(&missing, 64, 1).unwrap_err();
+        assert!(matches!(err, super::ConcatError::Io(_)));
+        assert!(err.to_string().contains("missing.png"), "{err}");
+
+        for path in paths {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_concat_with_layout() {
+        let imgs: Vec<_> = [(10, 20), (30, 5), (15, 15)]
+            .iter()
+            .map(|(width, height)| image::RgbImage::new(*width, *height))
+            .collect();
+
+        let (img, layout) =
+            super::concat_images_with_layout(&imgs, super::ConcatDirection::Vertical).unwrap();
+        assert_eq!(img.dimensions(), (30, 40));
+        assert_eq!(
+            layout,
+            vec![
+                super::Rect {
+                    x: 0,
+                    y: 0,
+                    width: 10,
+                    height: 20
+                },
+                super::Rect {
+                    x: 0,
+                    y: 20,
+                    width: 30,
+                    height: 5
+                },
+                super::Rect {
+                    x: 0,
+                    y: 25,
+                    width: 15,
+                    height: 15
+                },
+            ]
+        );
+
+        // every row of the output belongs to exactly one image
+        for y in 0..img.height() {
+            assert_eq!(layout.iter().filter(|rect| rect.contains(0, y)).count(), 1);
+        }
+        assert!(!layout[1].contains(30, 20));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_load_and_vert_concat_images_mmap() {
+        let paths: Vec<_> = (1..=4)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+
+        let mapped = super::load_and_vert_concat_images_mmap(&paths).unwrap();
+        let read = super::load_and_vert_concat_images(&paths).unwrap();
+        assert_eq!(mapped, read);
+
+        let missing = [PathBuf::from("./test/does_not_exist.png")];
+        assert!(matches!(
+            super::load_and_vert_concat_images_mmap(&missing),
+            Err(super::ConcatError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_u16() {
+        let first_path = temp_path("u16_first.png");
+        let second_path = temp_path("u16_second.png");
+        let first = image::ImageBuffer::from_pixel(3, 2, image::Rgb([0x1234u16, 0xabcd, u16::MAX]));
+        let second = image::ImageBuffer::from_fn(3, 1, |x, _| {
+            image::Rgb([x as u16 * 0x0101 + 0x8000, 1, 0])
+        });
+        first.save(&first_path).unwrap();
+        second.save(&second_path).unwrap();
+
+        let img =
+            super::load_and_vert_concat_images_u16(&[first_path.clone(), second_path.clone()])
+                .unwrap();
+        assert_eq!(img.dimensions(), (3, 3));
+        assert_eq!(*img.get_pixel(0, 0), image::Rgb([0x1234, 0xabcd, u16::MAX]));
+        assert_eq!(*img.get_pixel(2, 2), image::Rgb([0x8202, 1, 0]));
+
+        // 8-bit sources are rejected rather than silently widened
+        assert!(matches!(
+            super::load_and_vert_concat_images_u16(&[PathBuf::from("./test/1.png")]),
+            Err(super::ConcatError::Decode(_))
+        ));
+
+        std::fs::remove_file(first_path).unwrap();
+        std::fs::remove_file(second_path).unwrap();
+    }
+
+    #[test]
+    fn test_split_image() {
+        let imgs: Vec<_> = (0..6u8)
+            .map(|i| image::RgbImage::from_fn(4, 3, |x, y| image::Rgb([i, x as u8, y as u8])))
+            .collect();
+
+        let grid = super::grid_concat_images(&imgs, 3).unwrap();
+        let tiles = super::split_image(&grid, 2, 3);
+        assert_eq!(tiles, imgs);
+
+        // leftover pixels go to the leading tiles
+        let tiles = super::split_image(&image::RgbImage::new(5, 3), 2, 2);
+        let sizes: Vec<_> = tiles.iter().map(|tile| tile.dimensions()).collect();
+        assert_eq!(sizes, vec![(3, 2), (2, 2), (3, 1), (2, 1)]);
+
+        assert!(super::split_image(&grid, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_blit_transform() {
+        use super::{ImageBlit, Transform};
+        use image::Rgb;
+        let square = image::RgbImage::new(10, 20);
+        // 3x2 with a marker in the top left
+        let wide =
+            image::RgbImage::from_fn(3, 2, |x, y| Rgb([(x == 0 && y == 0) as u8 * 255, 0, 0]));
+        let marker = Rgb([255, 0, 0]);
+
+        // rotated image is 2 wide and 3 tall, so the horizontal concat is 12 wide
+        let blits = [
+            ImageBlit::new(&square, 0, 0),
+            ImageBlit::new(&wide, 10, 0).with_transform(Transform::Rotate90),
+        ];
+        let img = super::place_images_in_buffer(&blits).unwrap();
+        assert_eq!(img.dimensions(), (12, 20));
+        assert_eq!(*img.get_pixel(11, 0), marker);
+
+        // marker ends up in the corner each transform moves it to
+        for (transform, expected) in [
+            (Transform::None, (0, 0)),
+            (Transform::Rotate90, (1, 0)),
+            (Transform::Rotate180, (2, 1)),
+            (Transform::Rotate270, (0, 2)),
+            (Transform::FlipHorizontal, (2, 0)),
+            (Transform::FlipVertical, (0, 1)),
+        ] {
+            let blit = ImageBlit::new(&wide, 0, 0).with_transform(transform);
+            let img = super::place_images_in_buffer(&[blit]).unwrap();
+            assert_eq!(img.dimensions(), blit.dimensions());
+            assert_eq!(
+                *img.get_pixel(expected.0, expected.1),
+                marker,
+                "{transform:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_oriented() {
+        // Big endian TIFF header followed by a single IFD entry, Orientation (0x0112) = 6
+        let exif = [
+            b'M', b'M', 0, 42, 0, 0, 0, 8, // header, IFD at offset 8
+            0, 1, // one entry
+            0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0, // orientation, SHORT, count 1, value 6
+            0, 0, 0, 0, // no next IFD
+        ];
+
+        // 4x2 stored image with a marker in the top left, which rotates 90 degrees clockwise
+        let path = temp_path("oriented.png");
+        let stored = image::RgbImage::from_fn(4, 2, |x, y| {
+            image::Rgb([(x == 0 && y == 0) as u8 * 255, 0, 0])
+        });
+        {
+            let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+            let mut encoder = png::Encoder::new(file, 4, 2);
+            encoder.set_color(png::ColorType::Rgb);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_chunk(png::chunk::eXIf, &exif).unwrap();
+            writer.write_image_data(stored.as_raw()).unwrap();
+        }
+
+        let plain = super::load_and_vert_concat_images(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(plain.dimensions(), (4, 2));
+
+        let oriented =
+            super::load_and_vert_concat_images_oriented(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(oriented.dimensions(), (2, 4));
+        assert_eq!(*oriented.get_pixel(1, 0), image::Rgb([255, 0, 0]));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_images_to_gif() {
+        use image::AnimationDecoder;
+        let paths: Vec<_> = [(8, 6), (8, 6), (4, 10)]
+            .iter()
+            .enumerate()
+            .map(|(idx, (width, height))| {
+                let path = temp_path(&format!("gif_frame_{idx}.png"));
+                image::RgbImage::from_pixel(*width, *height, image::Rgb([80 * idx as u8, 0, 0]))
+                    .save(&path)
+                    .unwrap();
+                path
+            })
+            .collect();
+        let out = temp_path("frames.gif");
+
+        super::images_to_gif(&paths, &out, 100).unwrap();
+
+        let file = std::io::BufReader::new(std::fs::File::open(&out).unwrap());
+        let frames = image::codecs::gif::GifDecoder::new(file)
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(frames.len(), 3);
+        for frame in &frames {
+            assert_eq!(frame.buffer().dimensions(), (8, 10));
+            assert_eq!(frame.delay().numer_denom_ms(), (100, 1));
+        }
+
+        // smaller frames are padded with transparency
+        assert_eq!(frames[0].buffer().get_pixel(0, 9)[3], 0);
+        assert_eq!(
+            *frames[2].buffer().get_pixel(0, 9),
+            image::Rgba([160, 0, 0, 255])
+        );
+
+        for path in paths.into_iter().chain([out]) {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_concat_strict() {
+        let imgs = [
+            image::RgbImage::new(10, 5),
+            image::RgbImage::new(10, 8),
+            image::RgbImage::new(12, 5),
+        ];
+
+        let img =
+            super::concat_images_strict(&imgs[..2], super::ConcatDirection::Vertical).unwrap();
+        assert_eq!(img.dimensions(), (10, 13));
+
+        assert!(matches!(
+            super::concat_images_strict(&imgs, super::ConcatDirection::Vertical),
+            Err(super::ConcatError::DimensionMismatch {
+                index: 2,
+                expected: 10,
+                got: 12
+            })
+        ));
+        assert!(matches!(
+            super::concat_images_strict(&imgs, super::ConcatDirection::Horizontal),
+            Err(super::ConcatError::DimensionMismatch {
+                index: 1,
+                expected: 5,
+                got: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn test_load_iter() {
+        let paths: Vec<_> = (1..=3)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+        let lazy = super::load_and_vert_concat_images_iter(
+            (1..=3).map(|i| PathBuf::from(format!("./test/{i}.png"))),
+        )
+        .unwrap();
+        assert_eq!(lazy, super::load_and_vert_concat_images(&paths).unwrap());
+
+        assert!(matches!(
+            super::load_and_vert_concat_images_iter(std::iter::empty()),
+            Err(super::ConcatError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_load_and_concat_dir() {
+        let dir = temp_path("concat_dir");
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, marker) in [("1", 10), ("2", 20), ("10", 100)] {
+            image::RgbImage::from_pixel(2, 1, image::Rgb([marker, 0, 0]))
+                .save(dir.join(format!("{name}.png")))
+                .unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "not an image").unwrap();
+
+        let img =
+            super::load_and_concat_dir(&dir, "*.png", super::ConcatDirection::Vertical).unwrap();
+        assert_eq!(img.dimensions(), (2, 3));
+        let markers: Vec<_> = (0..3).map(|y| img.get_pixel(0, y)[0]).collect();
+        assert_eq!(markers, [10, 20, 100]);
+
+        let img =
+            super::load_and_concat_dir(&dir, "?.png", super::ConcatDirection::Horizontal).unwrap();
+        assert_eq!(img.dimensions(), (4, 1));
+
+        assert!(matches!(
+            super::load_and_concat_dir(&dir, "*.jpg", super::ConcatDirection::Vertical),
+            Err(super::ConcatError::Empty)
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_natural_sort_paths() {
+        let sorted = |names: &[&str]| {
+            let mut paths: Vec<_> = names.iter().map(PathBuf::from).collect();
+            super::natural_sort_paths(&mut paths);
+            paths
+                .iter()
+                .map(|path| path.to_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Mixed prefixes sort by prefix first, then by number
+        assert_eq!(
+            sorted(&["b2.png", "a10.png", "b10.png", "a2.png"]),
+            ["a2.png", "a10.png", "b2.png", "b10.png"]
+        );
+        // Zero padded numbers compare by value, least padded first on ties
+        assert_eq!(
+            sorted(&["img010.png", "img9.png", "img02.png", "img2.png"]),
+            ["img2.png", "img02.png", "img9.png", "img010.png"]
+        );
+        // Numbers anywhere in the path, including directories
+        assert_eq!(
+            sorted(&[
+                "shot10/2.png",
+                "shot2/10.png",
+                "shot2/9.png",
+                "10-final.png",
+                "9-draft.png"
+            ]),
+            [
+                "9-draft.png",
+                "10-final.png",
+                "shot2/9.png",
+                "shot2/10.png",
+                "shot10/2.png"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spacer() {
+        let red = image::Rgb([255, 0, 0]);
+        let blue = image::Rgb([0, 0, 255]);
+        let imgs = vec![
+            image::RgbImage::from_pixel(4, 2, red),
+            super::spacer(4, 3, blue),
+            image::RgbImage::from_pixel(4, 2, red),
+        ];
+        let img = super::concat_images(&imgs, super::ConcatDirection::Vertical).unwrap();
+        assert_eq!(img.dimensions(), (4, 7));
+
+        for (x, y, pixel) in img.enumerate_pixels() {
+            let expected = if (2..5).contains(&y) { blue } else { red };
+            assert_eq!(*pixel, expected, "pixel at {x},{y}");
+        }
+    }
+
+    #[test]
+    fn test_flatten_over_checkerboard() {
+        let mut img = image::RgbaImage::new(8, 4);
+        for x in 4..8 {
+            for y in 0..4 {
+                img.put_pixel(x, y, image::Rgba([10, 20, 30, 255]));
+            }
+        }
+
+        let flat = super::flatten_over_checkerboard(&img, 2);
+        assert_eq!(flat.dimensions(), (8, 4));
+        // Transparent left half alternates between the two grays every 2px
+        assert_eq!(*flat.get_pixel(0, 0), image::Rgb([204, 204, 204]));
+        assert_eq!(*flat.get_pixel(2, 0), image::Rgb([153, 153, 153]));
+        assert_eq!(*flat.get_pixel(2, 2), image::Rgb([204, 204, 204]));
+        // Opaque right half is untouched
+        for y in 0..4 {
+            assert_eq!(*flat.get_pixel(5, y), image::Rgb([10, 20, 30]));
+        }
+    }
+
+    #[test]
+    fn test_place_blended() {
+        let blue = image::RgbaImage::from_pixel(4, 4, image::Rgba([0, 0, 255, 255]));
+        let red = image::RgbaImage::from_pixel(2, 2, image::Rgba([255, 0, 0, 128]));
+        let blits = [
+            super::ImageBlit::new(&blue, 0, 0),
+            super::ImageBlit::new(&red, 1, 1),
+        ];
+
+        let img = super::place_images_in_buffer_blended(&blits).unwrap();
+        assert_eq!(img.dimensions(), (4, 4));
+        assert_eq!(*img.get_pixel(0, 0), image::Rgba([0, 0, 255, 255]));
+
+        // Half red over blue blends to purple
+        let [r, g, b, a] = img.get_pixel(1, 1).0;
+        assert!(
+            r.abs_diff(128) <= 1 && g == 0 && b.abs_diff(127) <= 1 && a >= 254,
+            "{:?}",
+            img.get_pixel(1, 1)
+        );
+
+        // copy_from would have left the half transparent red as is
+        let copied = super::place_images_in_buffer(&blits).unwrap();
+        assert_eq!(*copied.get_pixel(1, 1), image::Rgba([255, 0, 0, 128]));
+    }
+
+    #[test]
+    fn test_concat_dividers() {
+        use image::Rgb;
+        let white = Rgb([255, 255, 255]);
+        let red = Rgb([255, 0, 0]);
+        let imgs = vec![
+            image::RgbImage::from_pixel(6, 4, white),
+            image::RgbImage::from_pixel(6, 4, white),
+            image::RgbImage::from_pixel(6, 4, white),
+        ];
+        let options = super::ConcatOptions {
+            divider: Some(super::Border {
+                width: 1,
+                color: red,
+            }),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+
+        // Dividers add no space, they overwrite the first row of each following image
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.dimensions(), (6, 12));
+        for (_, y, pixel) in img.enumerate_pixels() {
+            let expected = if y == 4 || y == 8 { red } else { white };
+            assert_eq!(*pixel, expected, "row {y}");
+        }
+
+        // Thicker dividers centered on the middle of the gap between spaced images
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(4),
+            divider: Some(super::Border {
+                width: 2,
+                color: red,
+            }),
+            ..super::ConcatOptions::new(super::ConcatDirection::Horizontal)
+        };
+        let img = super::concat_images_with_options(&imgs[..2], &options).unwrap();
+        assert_eq!(img.dimensions(), (16, 4));
+        let row: Vec<_> = (0..16).map(|x| *img.get_pixel(x, 3)).collect();
+        assert_eq!(row[5], white);
+        assert_eq!(row[6], Rgb([0, 0, 0]));
+        assert_eq!(&row[7..9], &[red, red]);
+        assert_eq!(row[9], Rgb([0, 0, 0]));
+        assert_eq!(row[10], white);
+    }
+
+    #[test]
+    fn test_place_sized() {
+        use image::Rgb;
+        let white = Rgb([255, 255, 255]);
+        let red = Rgb([255, 0, 0]);
+        let img = image::RgbImage::from_pixel(4, 2, red);
+
+        // Smaller than the canvas, centered with the background around it
+        let blits = [super::ImageBlit::new(&img, 5, 3).with_origin(super::Anchor::Center)];
+        let canvas = super::place_images_in_buffer_sized(&blits, 10, 6, white).unwrap();
+        assert_eq!(canvas.dimensions(), (10, 6));
+        for (x, y, pixel) in canvas.enumerate_pixels() {
+            let inside = (3..7).contains(&x) && (2..4).contains(&y);
+            assert_eq!(*pixel, if inside { red } else { white }, "pixel at {x},{y}");
+        }
+
+        // Larger than the canvas, clipped to it
+        let big = image::RgbImage::from_pixel(20, 20, red);
+        let blits = [super::ImageBlit::new(&big, 2, 2)];
+        let canvas = super::place_images_in_buffer_sized(&blits, 10, 6, white).unwrap();
+        assert_eq!(canvas.dimensions(), (10, 6));
+        assert_eq!(*canvas.get_pixel(1, 1), white);
+        assert_eq!(*canvas.get_pixel(2, 2), red);
+        assert_eq!(*canvas.get_pixel(9, 5), red);
+    }
+
+    #[test]
+    fn test_center_in_canvas() {
+        use image::Rgb;
+        let black = Rgb([0, 0, 0]);
+        let red = Rgb([255, 0, 0]);
+        let img = image::RgbImage::from_pixel(3, 5, red);
+
+        let canvas = super::center_in_canvas(&img, 10, 8, black);
+        assert_eq!(canvas.dimensions(), (10, 8));
+        let (left, top) = ((10 - 3) / 2, (8 - 5) / 2);
+        for (x, y, pixel) in canvas.enumerate_pixels() {
+            let inside = (left..left + 3).contains(&x) && (top..top + 5).contains(&y);
+            assert_eq!(*pixel, if inside { red } else { black }, "pixel at {x},{y}");
+        }
+
+        // Larger than the canvas fills it completely
+        let canvas = super::center_in_canvas(&img, 2, 2, black);
+        assert!(canvas.pixels().all(|pixel| *pixel == red));
+    }
+
+    #[test]
+    fn test_concat_bytes_to_png() {
+        let buffers: Vec<_> = (1..=3)
+            .map(|i| std::fs::read(format!("./test/{i}.png")).unwrap())
+            .collect();
+        let slices: Vec<&[u8]> = buffers.iter().map(Vec::as_slice).collect();
+
+        let png = super::concat_bytes_to_png(&slices, super::ConcatDirection::Horizontal).unwrap();
+        let decoded = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+            .unwrap()
+            .into_rgb8();
+        let expected =
+            super::concat_images_from_bytes(&slices, super::ConcatDirection::Horizontal).unwrap();
+        assert_eq!(decoded.dimensions(), expected.dimensions());
+        assert_eq!(decoded, expected);
+
+        assert!(matches!(
+            super::concat_bytes_to_png(&[], super::ConcatDirection::Vertical),
+            Err(super::ConcatError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_concat_to_raw_rgba() {
+        let red = image::RgbImage::from_pixel(3, 2, image::Rgb([255, 0, 0]));
+        let blue = image::RgbaImage::from_pixel(2, 2, image::Rgba([0, 0, 255, 128]));
+        let imgs = [
+            image::DynamicImage::ImageRgb8(red),
+            image::DynamicImage::ImageRgba8(blue),
+        ];
+
+        let (bytes, width, height) =
+            super::concat_to_raw_rgba(&imgs, super::ConcatDirection::Vertical).unwrap();
+        assert_eq!((width, height), (3, 4));
+        assert_eq!(bytes.len(), (width * height * 4) as usize);
+
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * width + x) * 4) as usize;
+            &bytes[i..i + 4]
+        };
+        assert_eq!(pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(2, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(0, 3), [0, 0, 255, 128]);
+        // padding right of the narrower image is transparent
+        assert_eq!(pixel(2, 3), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_copy_image_into_matches_copy_from() {
+        use image::GenericImage;
+        let imgs: Vec<_> = (1..=8)
+            .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+            .collect();
+        let plans = [
+            super::get_concat_blits(&imgs, super::ConcatDirection::Vertical, 0, 0),
+            super::get_concat_blits(&imgs, super::ConcatDirection::Horizontal, 3, 7),
+            super::get_grid_blits(&imgs, 3, 0, 0),
+        ];
+
+        for blits in &plans {
+            // Same placement done pixel by pixel through the generic path
+            let (width, height) = super::blits_dimensions(blits).unwrap();
+            let mut expected = image::RgbImage::new(width, height);
+            for blit in blits {
+                expected.copy_from(blit.img, blit.x, blit.y).unwrap();
+            }
+
+            assert_eq!(super::place_images_in_buffer(blits).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_into() {
+        use image::Rgb;
+        let red = image::RgbImage::from_pixel(4, 3, Rgb([255, 0, 0]));
+        let blue = image::RgbImage::from_pixel(4, 3, Rgb([0, 0, 255]));
+        let small = image::RgbImage::from_pixel(2, 2, Rgb([0, 255, 0]));
+        let mut buffer = image::RgbImage::new(0, 0);
+
+        let imgs = [red, blue.clone()];
+        let blits = super::get_concat_blits(&imgs, super::ConcatDirection::Horizontal, 0, 0);
+        super::place_images_in_buffer_into(&blits, &mut buffer).unwrap();
+        assert_eq!(buffer, super::place_images_in_buffer(&blits).unwrap());
+        let ptr = buffer.as_raw().as_ptr();
+
+        // Same size plan reuses the allocation, and old contents don't leak through
+        let blits = [
+            super::ImageBlit::new(&blue, 0, 0),
+            super::ImageBlit::new(&small, 4, 0),
+        ];
+        super::place_images_in_buffer_into(&blits, &mut buffer).unwrap();
+        assert_eq!(buffer.dimensions(), (6, 3));
+        assert_eq!(buffer.as_raw().as_ptr(), ptr);
+        assert_eq!(buffer, super::place_images_in_buffer(&blits).unwrap());
+        assert_eq!(*buffer.get_pixel(5, 2), Rgb([0, 0, 0]));
+
+        // A smaller plan still fits in the same allocation
+        let blits = [super::ImageBlit::new(&small, 0, 0)];
+        super::place_images_in_buffer_into(&blits, &mut buffer).unwrap();
+        assert_eq!(buffer, small);
+        assert_eq!(buffer.as_raw().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_concat_max_pixels() {
+        let imgs = [image::RgbImage::new(2, 1), image::RgbImage::new(2, 1)];
+        // Would be a ~6GB buffer if it were allocated
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(1 << 30),
+            max_pixels: Some(1_000_000),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        assert!(matches!(
+            super::concat_images_with_options(&imgs, &options),
+            Err(super::ConcatError::ExceedsMaxPixels {
+                pixels,
+                max_pixels: 1_000_000
+            }) if pixels == 2 * ((1 << 30) + 2)
+        ));
+
+        // Right at the limit is fine
+        let options = super::ConcatOptions {
+            max_pixels: Some(4),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        assert_eq!(
+            super::concat_images_with_options(&imgs, &options)
+                .unwrap()
+                .dimensions(),
+            (2, 2)
+        );
+    }
+
+    #[test]
+    fn test_sort_paths_by_exif_time() {
+        // Big endian TIFF with IFD0 pointing to an Exif IFD holding only DateTimeOriginal
+        let exif = |timestamp: &str| {
+            let mut exif = vec![b'M', b'M', 0, 42, 0, 0, 0, 8];
+            exif.extend([0, 1, 0x87, 0x69, 0, 4, 0, 0, 0, 1, 0, 0, 0, 26, 0, 0, 0, 0]); // Exif IFD at 26
+            let count = timestamp.len() as u8 + 1;
+            exif.extend([
+                0, 1, 0x90, 0x03, 0, 2, 0, 0, 0, count, 0, 0, 0, 44, 0, 0, 0, 0,
+            ]); // ASCII at 44
+            exif.extend(timestamp.bytes().chain([0]));
+            exif
+        };
+        let write_png = |name: &str, exif: Option<Vec<u8>>| {
+            let path = temp_path(name);
+            let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+            let mut encoder = png::Encoder::new(file, 1, 1);
+            encoder.set_color(png::ColorType::Rgb);
+            let mut writer = encoder.write_header().unwrap();
+            if let Some(exif) = exif {
+                writer.write_chunk(png::chunk::eXIf, &exif).unwrap();
+            }
+            writer.write_image_data(&[0, 0, 0]).unwrap();
+            path
+        };
+
+        let later = write_png("exif_a.png", Some(exif("2021:05:01 10:00:00")));
+        let earlier = write_png("exif_b.png", Some(exif("2020:12:31 23:59:59")));
+        // No EXIF, falls back to its modification time of now
+        let untagged = write_png("exif_c.png", None);
+        // Out of range fields are ignored rather than overflowing, so this falls back too
+        let absurd = write_png(
+            "exif_d.png",
+            Some(exif("9223372036854775807:12:31 9223372036854775807:59:59")),
+        );
+
+        let mut paths = vec![
+            later.clone(),
+            untagged.clone(),
+            absurd.clone(),
+            earlier.clone(),
+        ];
+        super::sort_paths_by_exif_time(&mut paths);
+        assert_eq!(
+            paths,
+            [
+                earlier.clone(),
+                later.clone(),
+                untagged.clone(),
+                absurd.clone()
+            ]
+        );
+
+        for path in [later, earlier, untagged, absurd] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_concat_heterogeneous() {
+        use super::PixelFormat;
+        use image::{ColorType, DynamicImage};
+        let jpeg_like =
+            DynamicImage::ImageRgb8(image::RgbImage::from_pixel(4, 2, image::Rgb([255, 0, 0])));
+        let png_like = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            3,
+            2,
+            image::Rgba([0, 0, 255, 100]),
+        ));
+        let imgs = [jpeg_like, png_like];
+
+        for (target, color) in [
+            (PixelFormat::Rgb8, ColorType::Rgb8),
+            (PixelFormat::Rgba8, ColorType::Rgba8),
+            (PixelFormat::Luma16, ColorType::L16),
+            (PixelFormat::Rgba32F, ColorType::Rgba32F),
+        ] {
+            let img =
+                super::concat_heterogeneous(&imgs, super::ConcatDirection::Horizontal, target)
+                    .unwrap();
+            assert_eq!(img.color(), color);
+            assert_eq!((img.width(), img.height()), (7, 2));
+        }
+
+        // Alpha is dropped when converting to an opaque target
+        let img = super::concat_heterogeneous(
+            &imgs,
+            super::ConcatDirection::Horizontal,
+            PixelFormat::Rgb8,
+        )
+        .unwrap();
+        assert_eq!(
+            *img.as_rgb8().unwrap().get_pixel(5, 0),
+            image::Rgb([0, 0, 255])
+        );
+    }
+
+    #[test]
+    fn test_autocrop() {
+        use image::Rgb;
+        let white = Rgb([255, 255, 255]);
+        // 6x4 content padded by a near white border: 3 left, 1 right, 2 top, 5 bottom
+        let mut img = image::RgbImage::from_pixel(10, 11, white);
+        img.put_pixel(0, 0, Rgb([250, 252, 255]));
+        for x in 3..9 {
+            for y in 2..6 {
+                img.put_pixel(x, y, Rgb([20, 40, 60]));
+            }
+        }
+        // background colored hole inside the content is kept
+        img.put_pixel(5, 3, white);
+
+        let trimmed = super::autocrop(&img, white, 8.0);
+        assert_eq!(trimmed.dimensions(), (6, 4));
+        assert_eq!(*trimmed.get_pixel(0, 0), Rgb([20, 40, 60]));
+        assert_eq!(*trimmed.get_pixel(2, 1), white);
+
+        // Without tolerance the off white corner pixel stops the trim
+        assert_eq!(super::autocrop(&img, white, 0.0).dimensions(), (9, 6));
+        assert_eq!(
+            super::autocrop(&image::RgbImage::from_pixel(3, 3, white), white, 0.0).dimensions(),
+            (0, 0)
+        );
+
+        let path = temp_path("autocrop.png");
+        img.save(&path).unwrap();
+        let loaded = super::load_and_vert_concat_images_autocropped(
+            &[path.clone(), path.clone()],
+            white,
+            8.0,
+        )
+        .unwrap();
+        assert_eq!(loaded.dimensions(), (6, 8));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_column_concat_with_widths() {
+        use image::Rgb;
+        let colors = [
+            Rgb([255, 0, 0]),
+            Rgb([0, 255, 0]),
+            Rgb([0, 0, 255]),
+            Rgb([255, 255, 0]),
+        ];
+        let imgs: Vec<_> = [(10, 3), (4, 2), (8, 4), (3, 1)]
+            .iter()
+            .zip(colors)
+            .map(|((width, height), color)| image::RgbImage::from_pixel(*width, *height, color))
+            .collect();
+
+        let img = super::column_concat_images_with_widths(&imgs, &[6, 5]).unwrap();
+        assert_eq!(img.dimensions(), (11, 5));
+        // First column starts at 0 and crops the 10px wide image to 6
+        assert_eq!(*img.get_pixel(0, 0), colors[0]);
+        assert_eq!(*img.get_pixel(5, 0), colors[0]);
+        // Second column starts at the first column's width
+        assert_eq!(*img.get_pixel(6, 0), colors[2]);
+        assert_eq!(*img.get_pixel(10, 0), colors[2]);
+        // Narrower images are padded on the right
+        assert_eq!(*img.get_pixel(3, 3), colors[1]);
+        assert_eq!(*img.get_pixel(4, 3), Rgb([0, 0, 0]));
+        assert_eq!(*img.get_pixel(8, 4), colors[3]);
+        assert_eq!(*img.get_pixel(9, 4), Rgb([0, 0, 0]));
+
+        assert!(matches!(
+            super::column_concat_images_with_widths(&imgs, &[]),
+            Err(super::ConcatError::ColumnCountZero)
+        ));
+    }
+
+    #[test]
+    fn test_grid_row_align() {
+        use super::RowAlign;
+        let short = image::RgbImage::new(4, 4);
+        let tall = image::RgbImage::new(4, 11);
+        let imgs = [short.clone(), tall.clone(), short, tall];
+
+        let ys = |row_align| {
+            super::get_grid_blits_aligned(&imgs, 2, row_align, 0, 5)
+                .iter()
+                .map(|blit| blit.y)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ys(RowAlign::Top), [5, 5, 16, 16]);
+        // 7px of free space splits to 3 above, rounding towards the top
+        assert_eq!(ys(RowAlign::Middle), [8, 5, 19, 16]);
+        assert_eq!(ys(RowAlign::Bottom), [12, 5, 23, 16]);
+
+        let img = super::grid_concat_images_aligned(&imgs, 2, RowAlign::Middle).unwrap();
+        assert_eq!(img.dimensions(), (8, 22));
+    }
+
+    #[test]
+    fn test_suggest_columns() {
+        let squares = vec![image::RgbImage::new(10, 10); 9];
+        assert_eq!(super::suggest_columns(&squares, 1.0), 3);
+        assert_eq!(super::suggest_columns(&squares[..8], 2.0), 4);
+        // Tall images need more columns, 7 columns of 8 images is 70x80
+        let tall = vec![image::RgbImage::new(10, 40); 8];
+        assert_eq!(super::suggest_columns(&tall, 1.0), 7);
+        assert_eq!(super::suggest_columns::<image::Rgb<u8>>(&[], 1.0), 1);
+    }
+
+    #[test]
+    fn test_column_remainder() {
+        use super::ColumnRemainder;
+        use image::Rgb;
+        let imgs: Vec<_> = (0..7)
+            .map(|i| image::RgbImage::from_pixel(1, 1, Rgb([10 + i as u8, 0, 0])))
+            .collect();
+
+        // 7 into 3 columns is 3,2,2 from the front
+        let img =
+            super::column_concat_images_with_remainder(&imgs, 3, ColumnRemainder::Front).unwrap();
+        assert_eq!(img, super::column_concat_images(&imgs, 3).unwrap());
+        assert_eq!(img.dimensions(), (3, 3));
+        assert_eq!(*img.get_pixel(0, 2), Rgb([12, 0, 0]));
+        assert_eq!(*img.get_pixel(1, 0), Rgb([13, 0, 0]));
+        assert_eq!(*img.get_pixel(2, 2), Rgb([0, 0, 0]));
+
+        // and 2,2,3 from the back
+        let img =
+            super::column_concat_images_with_remainder(&imgs, 3, ColumnRemainder::Back).unwrap();
+        assert_eq!(img.dimensions(), (3, 3));
+        assert_eq!(*img.get_pixel(0, 2), Rgb([0, 0, 0]));
+        assert_eq!(*img.get_pixel(1, 0), Rgb([12, 0, 0]));
+        assert_eq!(*img.get_pixel(2, 2), Rgb([16, 0, 0]));
+
+        // A tall first image gets a column to itself instead of a 6px tall column
+        let mut ragged = vec![image::RgbImage::new(1, 4)];
+        ragged.extend(vec![image::RgbImage::new(1, 1); 6]);
+        assert_eq!(super::column_concat_images(&ragged, 3).unwrap().height(), 6);
+        let img = super::column_concat_images_with_remainder(&ragged, 3, ColumnRemainder::ByHeight)
+            .unwrap();
+        assert_eq!(img.dimensions(), (3, 4));
+
+        // More columns than images still leaves every image its own column
+        let img =
+            super::column_concat_images_with_remainder(&ragged[..2], 3, ColumnRemainder::ByHeight)
+                .unwrap();
+        assert_eq!(img.dimensions(), (3, 4));
+    }
+
+    #[test]
+    fn test_column_concat_balanced() {
+        use image::Rgb;
+        let heights = [9, 2, 2, 2, 8, 1, 3, 3];
+        let imgs: Vec<_> = heights
+            .iter()
+            .enumerate()
+            .map(|(i, height)| image::RgbImage::from_pixel(2, *height, Rgb([i as u8 + 1, 0, 0])))
+            .collect();
+
+        let naive = super::column_concat_images(&imgs, 3).unwrap();
+        let balanced = super::column_concat_images_balanced(&imgs, 3).unwrap();
+        assert_eq!(naive.height(), 13);
+        assert!(balanced.height() <= naive.height());
+        assert_eq!(balanced.dimensions(), (6, 11));
+
+        // Columns of 9 | 2 2 1 3 3 | 2 8
+        assert_eq!(*balanced.get_pixel(0, 0), Rgb([1, 0, 0]));
+        assert_eq!(*balanced.get_pixel(0, 9), Rgb([0, 0, 0]));
+        assert_eq!(*balanced.get_pixel(2, 4), Rgb([6, 0, 0]));
+        assert_eq!(*balanced.get_pixel(2, 10), Rgb([8, 0, 0]));
+        assert_eq!(*balanced.get_pixel(4, 2), Rgb([5, 0, 0]));
+
+        let img = super::column_concat_images_balanced(&imgs[..1], 2).unwrap();
+        assert_eq!(img.dimensions(), (4, 9));
+    }
+
+    #[test]
+    fn test_panorama_concat() {
+        use image::{Rgb, RgbImage};
+        let img1 = RgbImage::from_pixel(30, 4, Rgb([100, 0, 0]));
+        let img2 = RgbImage::from_pixel(20, 6, Rgb([200, 0, 0]));
+
+        let img = super::panorama_concat(&[img1, img2], 10).unwrap();
+        assert_eq!(img.dimensions(), (30 + 20 - 10, 6));
+        assert_eq!(*img.get_pixel(19, 0), Rgb([100, 0, 0]));
+        assert_eq!(*img.get_pixel(20, 0), Rgb([100, 0, 0]));
+        // Halfway through the overlap is an even mix of both images
+        assert_eq!(*img.get_pixel(25, 3), Rgb([150, 0, 0]));
+        assert_eq!(*img.get_pixel(30, 0), Rgb([200, 0, 0]));
+        // Below the shorter image only the taller one is drawn
+        assert_eq!(*img.get_pixel(25, 5), Rgb([200, 0, 0]));
+        assert_eq!(*img.get_pixel(5, 5), Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_load_cmyk_jpeg() {
+        use image::Rgb;
+        // 16x8 Adobe CMYK JPEG, red on the left and white on the right
+        let img = super::load_and_vert_concat_images(&[PathBuf::from("./test/cmyk.jpg")]).unwrap();
+        let close = |pixel: &Rgb<u8>, expected: [u8; 3]| {
+            pixel
+                .0
+                .iter()
+                .zip(expected)
+                .all(|(&a, b)| a.abs_diff(b) <= 8)
+        };
+        // Inverted CMYK would come out cyan and black instead
+        assert!(close(img.get_pixel(2, 2), [255, 0, 0]));
+        assert!(close(img.get_pixel(12, 5), [255, 255, 255]));
+
+        // Without the APP14 marker the same samples are plain CMYK, which is rejected
+        let bytes = std::fs::read("./test/cmyk.jpg").unwrap();
+        let app14_len = u16::from_be_bytes([bytes[4], bytes[5]]) as usize;
+        assert_eq!(&bytes[2..4], [0xFF, 0xEE]);
+        let plain = [&bytes[..2], &bytes[4 + app14_len..]].concat();
+        let plain_path = temp_path("plain_cmyk.jpg");
+        std::fs::write(&plain_path, &plain).unwrap();
+
+        let img_result = super::load_and_vert_concat_images(std::slice::from_ref(&plain_path));
+        std::fs::remove_file(&plain_path).unwrap();
+        assert!(matches!(
+            img_result,
+            Err(super::ConcatError::Decode(image::ImageError::Unsupported(
+                _
+            )))
+        ));
+        let img_result =
+            super::concat_images_from_bytes(&[plain], super::ConcatDirection::Vertical);
+        assert!(matches!(
+            img_result,
+            Err(super::ConcatError::Decode(image::ImageError::Unsupported(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_plan_concat() {
+        let imgs: Vec<_> = (1..=3)
+            .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+            .collect();
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(4),
+            alignment: super::Alignment::Center,
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+
+        let plan = super::plan_concat(&imgs, &options).unwrap();
+        let blits = super::get_concat_blits_with_options(&imgs, &options, 0, 0);
+        assert_eq!(plan.images.len(), blits.len());
+        for (idx, (planned, blit)) in plan.images.iter().zip(&blits).enumerate() {
+            assert_eq!(planned.index, idx);
+            assert_eq!((planned.x, planned.y), (blit.x, blit.y));
+            assert_eq!((planned.width, planned.height), imgs[idx].dimensions());
+        }
+
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!((plan.width, plan.height), img.dimensions());
+    }
+
+    #[test]
+    fn test_apply_tint() {
+        use image::{Rgb, Rgba};
+        let mut img = image::RgbImage::from_pixel(2, 2, Rgb([0, 100, 200]));
+        super::apply_tint(&mut img, Rgb([200, 200, 0]), 0.25);
+        assert_eq!(*img.get_pixel(1, 1), Rgb([50, 125, 150]));
+
+        let mut img = image::RgbImage::from_pixel(2, 2, Rgb([0, 100, 200]));
+        super::apply_tint(&mut img, Rgb([200, 200, 0]), 0.75);
+        assert_eq!(*img.get_pixel(1, 1), Rgb([150, 175, 50]));
+
+        // Alpha stays put
+        let mut img = image::RgbaImage::from_pixel(2, 2, Rgba([0, 0, 0, 0]));
+        super::apply_tint(&mut img, Rgba([200, 0, 0, 255]), 0.5);
+        assert_eq!(*img.get_pixel(0, 0), Rgba([100, 0, 0, 0]));
+
+        // Tinting through options covers the background between images too
+        let imgs = [image::RgbImage::new(2, 2), image::RgbImage::new(2, 2)];
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(2),
+            background: Some(Rgb([100, 100, 100])),
+            tint: Some(super::Tint {
+                color: Rgb([200, 0, 0]),
+                strength: 0.5,
+            }),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(*img.get_pixel(0, 0), Rgb([100, 0, 0]));
+        assert_eq!(*img.get_pixel(0, 2), Rgb([150, 50, 50]));
+    }
+
+    #[test]
+    fn test_overlay_watermark() {
+        use image::{Rgba, RgbaImage};
+        let close = |pixel: &Rgba<u8>, expected: [u8; 4]| {
+            pixel
+                .0
+                .iter()
+                .zip(expected)
+                .all(|(&a, b)| a.abs_diff(b) <= 2)
+        };
+        let watermark = RgbaImage::from_pixel(2, 3, Rgba([255, 0, 0, 255]));
+
+        let mut base = RgbaImage::from_pixel(10, 10, Rgba([0, 0, 255, 255]));
+        super::overlay_watermark(&mut base, &watermark, super::Corner::BottomRight, 1, 0.5);
+        // 2x3 watermark ending 1px in from the bottom right, at x 7..9 and y 6..9
+        assert!(close(base.get_pixel(7, 6), [128, 0, 127, 255]));
+        assert!(close(base.get_pixel(8, 8), [128, 0, 127, 255]));
+        assert_eq!(*base.get_pixel(9, 9), Rgba([0, 0, 255, 255]));
+        assert_eq!(*base.get_pixel(6, 6), Rgba([0, 0, 255, 255]));
+        assert_eq!(*base.get_pixel(7, 5), Rgba([0, 0, 255, 255]));
+
+        let mut base = RgbaImage::from_pixel(10, 10, Rgba([0, 0, 255, 255]));
+        super::overlay_watermark(&mut base, &watermark, super::Corner::TopLeft, 0, 1.0);
+        assert_eq!(*base.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*base.get_pixel(1, 2), Rgba([255, 0, 0, 255]));
+        assert_eq!(*base.get_pixel(2, 0), Rgba([0, 0, 255, 255]));
+
+        // The builder applies it after concatenating, so it lands in the corner of the whole output
+        let img = super::ConcatBuilder::new()
+            .add_image(RgbaImage::from_pixel(10, 10, Rgba([0, 0, 255, 255])))
+            .add_image(RgbaImage::from_pixel(10, 10, Rgba([0, 0, 255, 255])))
+            .watermark(watermark.clone(), super::Corner::BottomLeft, 0, 1.0)
+            .build()
+            .unwrap();
+        assert_eq!(*img.get_pixel(0, 19), Rgba([255, 0, 0, 255]));
+        assert_eq!(*img.get_pixel(0, 16), Rgba([0, 0, 255, 255]));
+
+        // Each watermark is kept, not just the last one
+        let green = RgbaImage::from_pixel(2, 3, Rgba([0, 255, 0, 255]));
+        let img = super::ConcatBuilder::new()
+            .add_image(RgbaImage::from_pixel(10, 10, Rgba([0, 0, 255, 255])))
+            .watermark(watermark, super::Corner::TopLeft, 0, 1.0)
+            .watermark(green, super::Corner::BottomRight, 0, 1.0)
+            .build()
+            .unwrap();
+        assert_eq!(*img.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*img.get_pixel(9, 9), Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn test_concat_builder_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<super::ConcatBuilder<image::Rgb<u8>>>();
+        assert_send::<super::ConcatBuilder<image::Rgba<u8>>>();
+    }
+
+    #[test]
+    fn test_image_dimensions_fast() {
+        for i in 1..=8 {
+            let path = PathBuf::from(format!("./test/{i}.png"));
+            let dimensions = super::image_dimensions_fast(&path).unwrap();
+            assert_eq!(dimensions, image::image_dimensions(&path).unwrap());
+        }
+        let dimensions =
+            super::image_dimensions_fast(std::path::Path::new("./test/cmyk.jpg")).unwrap();
+        assert_eq!(dimensions, (16, 8));
+
+        let missing = super::image_dimensions_fast(std::path::Path::new("./test/missing.png"));
+        assert!(matches!(missing, Err(super::ConcatError::Io(_))));
+    }
+
+    #[test]
+    fn test_load_and_vert_concat_images_skip_errors() {
+        let bytes = std::fs::read("./test/2.png").unwrap();
+        let corrupt = temp_path("skip_errors_corrupt.png");
+        std::fs::write(&corrupt, &bytes[..bytes.len() / 2]).unwrap();
+
+        let paths = [PathBuf::from("./test/1.png"), corrupt.clone()];
+        let img_result = super::load_and_vert_concat_images_skip_errors(&paths);
+        std::fs::remove_file(&corrupt).unwrap();
+
+        let (img, failures) = img_result.unwrap();
+        assert_eq!(img, image::open("./test/1.png").unwrap().into_rgb8());
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, corrupt);
+        assert!(matches!(failures[0].1, super::ConcatError::Decode(_)));
+
+        // With nothing loadable there's nothing to concatenate
+        let img_result = super::load_and_vert_concat_images_skip_errors(&[corrupt]);
+        assert!(matches!(img_result, Err(super::ConcatError::Empty)));
+    }
+
+    #[test]
+    fn test_concat_images_with_percent_spacing() {
+        let imgs = [
+            image::RgbImage::new(100, 100),
+            image::RgbImage::new(100, 100),
+        ];
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Percent(10.0),
+            ..super::ConcatOptions::new(super::ConcatDirection::Horizontal)
+        };
+        let blits = super::get_concat_blits_with_options(&imgs, &options, 0, 0);
+        assert_eq!(blits[1].x, 110);
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.dimensions(), (210, 100));
+
+        // A percentage of the average along the concat axis, here heights of 50 and 150
+        let imgs = [image::RgbImage::new(10, 50), image::RgbImage::new(10, 150)];
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Percent(10.0),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.dimensions(), (10, 210));
+    }
+
+    #[test]
+    fn test_round_corners() {
+        use image::{Rgba, RgbaImage};
+        let mut img = RgbaImage::from_pixel(20, 10, Rgba([10, 20, 30, 255]));
+        super::round_corners(&mut img, 4);
+        for (x, y) in [(0, 0), (19, 0), (0, 9), (19, 9)] {
+            assert_eq!(img.get_pixel(x, y)[3], 0);
+        }
+        // The curve is antialiased, so its edge is partly transparent
+        let edge = img.get_pixel(1, 1)[3];
+        assert!(edge > 0 && edge < 255);
+        assert_eq!(*img.get_pixel(10, 5), Rgba([10, 20, 30, 255]));
+        assert_eq!(*img.get_pixel(4, 0), Rgba([10, 20, 30, 255]));
+        assert_eq!(*img.get_pixel(0, 4), Rgba([10, 20, 30, 255]));
+
+        // Through options each image's corners show the background
+        let imgs = vec![RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255])); 2];
+        let options = super::ConcatOptions {
+            corner_radius: 3,
+            background: Some(Rgba([0, 0, 255, 255])),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(*img.get_pixel(0, 0), Rgba([0, 0, 255, 255]));
+        assert_eq!(*img.get_pixel(9, 9), Rgba([0, 0, 255, 255]));
+        assert_eq!(*img.get_pixel(0, 10), Rgba([0, 0, 255, 255]));
+        assert_eq!(*img.get_pixel(5, 5), Rgba([255, 0, 0, 255]));
+        assert_eq!(*img.get_pixel(5, 10), Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_concat_images_with_shadow() {
+        use image::{Rgba, RgbaImage};
+        let imgs = [RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255]))];
+        let options = super::ConcatOptions {
+            shadow: Some(super::Shadow {
+                offset_x: 4,
+                offset_y: 4,
+                blur: 2,
+                color: Rgba([0, 0, 0, 255]),
+                opacity: 0.5,
+            }),
+            ..super::ConcatOptions::new(super::ConcatDirection::Vertical)
+        };
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        // Grown by the offset plus blur on the right and bottom only
+        assert_eq!(img.dimensions(), (16, 16));
+        assert_eq!(*img.get_pixel(5, 5), Rgba([255, 0, 0, 255]));
+
+        // Below and right of the image is semi-transparent shadow, fading out at its blurred edge
+        let shadow = img.get_pixel(12, 12)[3];
+        assert!(shadow > 0 && shadow < 255);
+        let edge = img.get_pixel(15, 15)[3];
+        assert!(edge > 0 && edge < shadow);
+        // Nothing is cast up or to the left
+        assert_eq!(img.get_pixel(12, 1)[3], 0);
+        assert_eq!(img.get_pixel(1, 12)[3], 0);
+
+        let plan = super::plan_concat(&imgs, &options).unwrap();
+        assert_eq!((plan.width, plan.height), img.dimensions());
+    }
+
+    #[test]
+    fn test_blur() {
+        use image::{Rgb, RgbImage};
+        let img = image::open("./test/1.png").unwrap().into_rgb8();
+        assert_ne!(super::blur(&img, 2.0), img);
+        assert_eq!(super::blur(&img, 0.0), img);
+        assert_ne!(super::box_blur(&img, 2), img);
+        assert_eq!(super::box_blur(&img, 0), img);
+
+        // A flat image stays flat and an edge is averaged across the window
+        let flat = RgbImage::from_pixel(8, 8, Rgb([100, 150, 200]));
+        assert_eq!(super::box_blur(&flat, 3), flat);
+        let mut edge = RgbImage::new(8, 1);
+        edge.put_pixel(4, 0, Rgb([90, 0, 0]));
+        let blurred = super::box_blur(&edge, 1);
+        assert_eq!(*blurred.get_pixel(3, 0), Rgb([30, 0, 0]));
+        assert_eq!(*blurred.get_pixel(5, 0), Rgb([30, 0, 0]));
+        assert_eq!(*blurred.get_pixel(6, 0), Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_blur_gutters() {
+        use image::{Rgb, RgbImage};
+        let imgs = [
+            RgbImage::from_pixel(8, 8, Rgb([255, 0, 0])),
+            RgbImage::from_pixel(8, 8, Rgb([0, 0, 255])),
+        ];
+        let options = super::ConcatOptions {
+            spacing: super::Spacing::Fixed(6),
+            ..super::ConcatOptions::new(super::ConcatDirection::Horizontal)
+        };
+        let blits = super::get_concat_blits_with_options(&imgs, &options, 0, 0);
+        let mut img = super::place_images_in_buffer(&blits).unwrap();
+        super::blur_gutters(&mut img, &blits, 2.0).unwrap();
+
+        // Images stay sharp while the gutter picks up their colors
+        assert_eq!(*img.get_pixel(7, 4), Rgb([255, 0, 0]));
+        assert_eq!(*img.get_pixel(14, 4), Rgb([0, 0, 255]));
+        assert!(img.get_pixel(8, 4)[0] > 0);
+        assert!(img.get_pixel(13, 4)[2] > 0);
+    }
+
+    #[test]
+    fn test_write_concat() {
+        use image::{ImageFormat, Rgba, RgbaImage};
+        let imgs = [
+            RgbaImage::from_pixel(4, 3, Rgba([255, 0, 0, 255])),
+            RgbaImage::from_pixel(4, 5, Rgba([0, 0, 255, 128])),
+        ];
+        let img = super::concat_images(&imgs, super::ConcatDirection::Vertical).unwrap();
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        super::write_concat(&img, &mut cursor, ImageFormat::Png).unwrap();
+        let decoded =
+            image::load_from_memory_with_format(cursor.get_ref(), ImageFormat::Png).unwrap();
+        assert_eq!(decoded.into_rgba8(), img);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_vert_concat_parallel_bounded() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let paths: Vec<_> = (1..=8)
+            .map(|i| PathBuf::from(format!("./test/{i}.png")))
+            .collect();
+        let serial = super::load_and_vert_concat_images(&paths).unwrap();
+
+        for max_in_flight in [1, 3] {
+            let peak = AtomicUsize::new(0);
+            let bounded = super::vert_concat_parallel_bounded(&paths, max_in_flight, |alive| {
+                peak.fetch_max(alive, Ordering::SeqCst);
+            })
+            .unwrap();
+
+            assert_eq!(serial, bounded);
+            assert!(peak.load(Ordering::SeqCst) <= max_in_flight);
+        }
+        let unbounded = super::load_and_vert_concat_images_parallel_bounded(&paths, 0).unwrap();
+        assert_eq!(serial, unbounded);
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_src_rect() {
+        use image::{Rgb, RgbImage};
+
+        // Quadrants of a 20x10 image in four colors
+        let img = RgbImage::from_fn(20, 10, |x, y| match (x < 10, y < 5) {
+            (true, true) => Rgb([255, 0, 0]),
+            (false, true) => Rgb([0, 255, 0]),
+            (true, false) => Rgb([0, 0, 255]),
+            (false, false) => Rgb([255, 255, 255]),
+        });
+        let top_left = super::Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 5,
+        };
+
+        let blits = [
+            super::ImageBlit::new(&img, 0, 0).with_src_rect(top_left),
+            super::ImageBlit::new(&img, 10, 0).with_src_rect(top_left),
+        ];
+        let buffer = super::place_images_in_buffer(&blits).unwrap();
+        assert_eq!(buffer.dimensions(), (20, 5));
+        assert!(buffer.pixels().all(|pixel| *pixel == Rgb([255, 0, 0])));
+
+        // Other quadrants and transformed crops go through the same region
+        let bottom_right = super::Rect {
+            x: 10,
+            y: 5,
+            ..top_left
+        };
+        let blit = super::ImageBlit::new(&img, 0, 0)
+            .with_src_rect(bottom_right)
+            .with_transform(super::Transform::Rotate90);
+        assert_eq!(blit.dimensions(), (5, 10));
+        let buffer = super::place_images_in_buffer(&[blit]).unwrap();
+        assert!(buffer.pixels().all(|pixel| *pixel == Rgb([255, 255, 255])));
+
+        // Rects hanging off the image are clamped to it
+        let overhanging = super::Rect {
+            x: 15,
+            y: 8,
+            width: 100,
+            height: 100,
+        };
+        let blit = super::ImageBlit::new(&img, 0, 0).with_src_rect(overhanging);
+        assert_eq!(blit.dimensions(), (5, 2));
+    }
+
+    #[test]
+    fn test_montage() {
+        use super::MontageTarget;
+
+        let imgs = vec![image::RgbImage::new(10, 10); 9];
+
+        let square = super::montage(&imgs, MontageTarget::AutoSquare).unwrap();
+        assert_eq!(square.dimensions(), (30, 30));
+        // 2 columns needs 5 rows, the last holding a single image
+        let cols = super::montage(&imgs, MontageTarget::FixedCols(2)).unwrap();
+        assert_eq!(cols.dimensions(), (20, 50));
+        let rows = super::montage(&imgs, MontageTarget::FixedRows(2)).unwrap();
+        assert_eq!(rows.dimensions(), (50, 20));
+
+        assert!(matches!(
+            super::montage(&imgs, MontageTarget::FixedRows(0)),
+            Err(super::ConcatError::ColumnCountZero)
+        ));
+    }
+
+    #[test]
+    fn test_padding_is_zero_filled() {
+        use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+        let imgs = [
+            RgbaImage::from_pixel(4, 8, Rgba([255, 255, 255, 255])),
+            RgbaImage::from_pixel(4, 2, Rgba([255, 255, 255, 255])),
+        ];
+        let blits = super::get_concat_blits(&imgs, super::ConcatDirection::Horizontal, 0, 0);
+        let img = super::place_images_in_buffer(&blits).unwrap();
+        // below the short image, padded out to the tall one's height
+        assert_eq!(img.get_pixel(6, 5), &Rgba([0, 0, 0, 0]));
+
+        // the same goes for padding from alignment and spacing in concat_images_with_options
+        let imgs = vec![
+            RgbaImage::from_pixel(4, 4, Rgba([9, 9, 9, 255])),
+            RgbaImage::new(8, 1),
+        ];
+        let mut options = super::ConcatOptions::new(super::ConcatDirection::Vertical);
+        options.spacing = super::Spacing::Fixed(2);
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.get_pixel(6, 0)[3], 0);
+        assert_eq!(img.get_pixel(0, 5)[3], 0);
+
+        let imgs = vec![
+            RgbImage::from_pixel(4, 4, Rgb([9, 9, 9])),
+            RgbImage::new(8, 1),
+        ];
+        let options = super::ConcatOptions::new(super::ConcatDirection::Vertical);
+        let img = super::concat_images_with_options(&imgs, &options).unwrap();
+        assert_eq!(img.get_pixel(6, 0), &Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_pack_sprite_sheet() {
+        use image::{Rgba, RgbaImage};
+
+        let sizes = [(10, 6), (7, 9), (12, 4), (5, 5), (20, 3)];
+        let named: Vec<_> = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &(width, height))| {
+                let color = Rgba([i as u8 * 50, 255 - i as u8 * 50, 7, 255]);
+                (
+                    format!("sprite{i}"),
+                    RgbaImage::from_pixel(width, height, color),
+                )
+            })
+            .collect();
+
+        let (sheet, atlas) = super::pack_sprite_sheet(&named, 25).unwrap();
+        assert_eq!((atlas.width, atlas.height), sheet.dimensions());
+        assert_eq!(atlas.sprites.len(), named.len());
+
+        for (name, img) in &named {
+            let rect = atlas.sprites[name];
+            assert_eq!((rect.width, rect.height), img.dimensions());
+            let region = image::imageops::crop_imm(&sheet, rect.x, rect.y, rect.width, rect.height);
+            assert_eq!(
+                region.to_image(),
+                *img,
+                "{name} doesn't match its rect {rect:?}"
+            );
+        }
+    }
+
+    /// Pseudo random mix of sprite sizes from 4 to 35px on each side
+    fn assorted_sizes() -> Vec<(u32, u32)> {
+        (0..40u32)
+            .map(|i| (4 + (i * 17 + 3) % 32, 4 + (i * 29 + 11) % 32))
+            .collect()
+    }
+
+    #[test]
+    fn test_pack_bins() {
+        use image::{Rgba, RgbaImage};
+
+        let imgs: Vec<_> = assorted_sizes()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (width, height))| {
+                RgbaImage::from_pixel(width, height, Rgba([i as u8, 0, 0, 255]))
+            })
+            .collect();
+        let sprite_area: u32 = imgs.iter().map(|img| img.width() * img.height()).sum();
+
+        let bins = super::pack_bins(&imgs, (128, 128)).unwrap();
+        let mut packed: Vec<_> = bins
+            .iter()
+            .flat_map(|(_, placed)| placed)
+            .map(|rect| rect.index)
+            .collect();
+        packed.sort_unstable();
+        assert_eq!(packed, (0..imgs.len()).collect::<Vec<_>>());
+        // no more bins than are needed to hold the sprites with a little waste
+        assert!(bins.len() as u32 <= sprite_area.div_ceil(128 * 128 * 85 / 100));
+
+        for (bin, placed) in &bins {
+            assert_eq!(bin.dimensions(), (128, 128));
+            for (i, a) in placed.iter().enumerate() {
+                assert!(a.x + a.width <= 128 && a.y + a.height <= 128);
+                let source = &imgs[a.index];
+                let region = image::imageops::crop_imm(bin, a.x, a.y, a.width, a.height);
+                assert_eq!(region.to_image(), *source);
+                for b in &placed[i + 1..] {
+                    let apart = a.x + a.width <= b.x
+                        || b.x + b.width <= a.x
+                        || a.y + a.height <= b.y
+                        || b.y + b.height <= a.y;
+                    assert!(apart, "{a:?} overlaps {b:?}");
+                }
+            }
+        }
+
+        let too_big = vec![RgbaImage::new(10, 10), RgbaImage::new(200, 10)];
+        assert!(matches!(
+            super::pack_bins(&too_big, (128, 128)),
+            Err(super::ConcatError::TooLargeForBin { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_pack_sprite_sheet_is_tight() {
+        let named: Vec<_> = assorted_sizes()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (width, height))| (format!("{i}"), image::RgbaImage::new(width, height)))
+            .collect();
+        let sprite_area: u32 = named
+            .iter()
+            .map(|(_, img)| img.width() * img.height())
+            .sum();
+
+        let (sheet, _) = super::pack_sprite_sheet(&named, 256).unwrap();
+        let sheet_area = sheet.width() * sheet.height();
+        assert!(
+            sheet_area * 100 < sprite_area * 120,
+            "{sheet_area} px sheet for {sprite_area} px"
+        );
+    }
+
+    #[test]
+    fn test_grid_concat_images_striped() {
+        use image::{Rgb, RgbImage};
+
+        let white = Rgb([255, 255, 255]);
+        let (even, odd) = (Rgb([10, 10, 10]), Rgb([20, 20, 20]));
+        // 2 columns of 3 rows, one image in each row shorter than its neighbour
+        let imgs: Vec<_> = [(4, 6), (4, 3), (4, 2), (4, 5), (4, 7), (4, 1)]
+            .iter()
+            .map(|&(width, height)| RgbImage::from_pixel(width, height, white))
+            .collect();
+        let mut stripes = super::Stripes {
+            axis: super::StripeAxis::Rows,
+            even,
+            odd,
+        };
+
+        let img = super::grid_concat_images_striped(&imgs, 2, stripes).unwrap();
+        assert_eq!(img.dimensions(), (8, 6 + 5 + 7));
+        // background under the shorter image of each row
+        assert_eq!(img.get_pixel(5, 3), &even);
+        assert_eq!(img.get_pixel(1, 6 + 2), &odd);
+        assert_eq!(img.get_pixel(5, 6 + 5 + 1), &even);
+        // images are drawn over the stripes
+        assert_eq!(img.get_pixel(5, 0), &white);
+
+        stripes.axis = super::StripeAxis::Columns;
+        let img = super::grid_concat_images_striped(&imgs, 2, stripes).unwrap();
+        assert_eq!(img.get_pixel(1, 6 + 2), &even);
+        assert_eq!(img.get_pixel(5, 3), &odd);
+    }
+
+    #[test]
+    fn test_load_and_concat_with_gif_frames() {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame, Rgba, RgbaImage};
+
+        let colors = [
+            Rgba([255, 0, 0, 255]),
+            Rgba([0, 255, 0, 255]),
+            Rgba([0, 0, 255, 255]),
+        ];
+        let gif_path = temp_path("gif_frames_concat.gif");
+        {
+            let mut encoder = GifEncoder::new(std::fs::File::create(&gif_path).unwrap());
+            let delay = Delay::from_numer_denom_ms(100, 1);
+            let frames = colors
+                .iter()
+                .map(|&color| Frame::from_parts(RgbaImage::from_pixel(6, 4, color), 0, 0, delay));
+            encoder.encode_frames(frames).unwrap();
+        }
+
+        let paths = [gif_path.clone(), PathBuf::from("./test/1.png")];
+        let img = super::load_and_concat_with_gif_frames(&paths, super::ConcatDirection::Vertical)
+            .unwrap();
+        std::fs::remove_file(&gif_path).unwrap();
+
+        // 3 frames stacked above the png
+        assert_eq!(img.dimensions(), (422, 3 * 4 + 32));
+        for (i, color) in colors.iter().enumerate() {
+            assert_eq!(img.get_pixel(3, i as u32 * 4 + 2), color);
+        }
+    }
+
+    #[test]
+    fn test_concat_2x2() {
+        use image::{Rgb, RgbImage};
+
+        let (red, green, blue, white) = (
+            Rgb([255, 0, 0]),
+            Rgb([0, 255, 0]),
+            Rgb([0, 0, 255]),
+            Rgb([255, 255, 255]),
+        );
+        let top_left = RgbImage::from_pixel(10, 4, red);
+        let top_right = RgbImage::from_pixel(3, 6, green);
+        let bottom_left = RgbImage::from_pixel(5, 5, blue);
+        let bottom_right = RgbImage::from_pixel(8, 2, white);
+
+        let img = super::concat_2x2(&top_left, &top_right, &bottom_left, &bottom_right).unwrap();
+        // left column is 10 wide and the top row 6 tall
+        assert_eq!(img.dimensions(), (10 + 8, 6 + 5));
+        assert_eq!(img.get_pixel(9, 3), &red);
+        assert_eq!(img.get_pixel(10, 5), &green);
+        assert_eq!(img.get_pixel(0, 6), &blue);
+        assert_eq!(img.get_pixel(17, 7), &white);
+        // padding within a quadrant
+        assert_eq!(img.get_pixel(9, 5), &Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_grid_concat_spans() {
+        use image::{Rgb, RgbImage};
+
+        // a 2x2 hero taller than its span with three single cells around it
+        let imgs = vec![
+            RgbImage::from_pixel(20, 25, Rgb([255, 0, 0])),
+            RgbImage::from_pixel(10, 10, Rgb([0, 255, 0])),
+            RgbImage::from_pixel(10, 10, Rgb([0, 0, 255])),
+            RgbImage::from_pixel(10, 10, Rgb([255, 255, 255])),
+        ];
+        let hero = super::GridSpan {
+            col: 0,
+            row: 0,
+            colspan: 2,
+            rowspan: 2,
+        };
+        let spans = [
+            hero,
+            super::GridSpan::cell(2, 0),
+            super::GridSpan::cell(2, 1),
+            super::GridSpan::cell(0, 2),
+        ];
+
+        let blits = super::get_span_blits(&imgs, &spans, 10, 10, 0, 0);
+        for (i, a) in blits.iter().enumerate() {
+            for b in &blits[i + 1..] {
+                let (aw, ah) = a.dimensions();
+                let (bw, bh) = b.dimensions();
+                let apart =
+                    a.x + aw <= b.x || b.x + bw <= a.x || a.y + ah <= b.y || b.y + bh <= a.y;
+                assert!(
+                    apart,
+                    "blits at ({}, {}) and ({}, {}) overlap",
+                    a.x, a.y, b.x, b.y
+                );
+            }
+        }
+
+        let img = super::grid_concat_spans(&imgs, &spans, 10, 10).unwrap();
+        assert_eq!(img.dimensions(), (30, 30));
+        // the hero covers exactly its 2x2 block, cropped from 25px tall
+        assert!((0..20).all(|x| (0..20).all(|y| img.get_pixel(x, y) == &Rgb([255, 0, 0]))));
+        assert_eq!(img.get_pixel(5, 25), &Rgb([255, 255, 255]));
+        assert_eq!(img.get_pixel(25, 5), &Rgb([0, 255, 0]));
+        assert_eq!(img.get_pixel(25, 15), &Rgb([0, 0, 255]));
+        // the cells no span covers stay empty
+        assert_eq!(img.get_pixel(15, 25), &Rgb([0, 0, 0]));
+        assert_eq!(img.get_pixel(25, 25), &Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_normalize_to_smallest() {
+        use image::imageops::FilterType;
+        use image::RgbImage;
+
+        let imgs = vec![
+            RgbImage::new(40, 20),
+            RgbImage::new(10, 30),
+            RgbImage::new(25, 25),
+        ];
+
+        let by_width =
+            super::normalize_to_smallest(&imgs, super::Axis::Width, FilterType::Triangle);
+        let dimensions: Vec<_> = by_width.iter().map(|img| img.dimensions()).collect();
+        assert_eq!(dimensions, vec![(10, 5), (10, 30), (10, 10)]);
+
+        let by_height =
+            super::normalize_to_smallest(&imgs, super::Axis::Height, FilterType::Triangle);
+        assert!(by_height.iter().all(|img| img.height() == 20));
+        // nothing grows on either axis
+        for normalized in [&by_width, &by_height] {
+            for (img, normalized) in imgs.iter().zip(normalized) {
+                assert!(normalized.width() <= img.width() && normalized.height() <= img.height());
+            }
+        }
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn test_add_title_strip() {
+        use super::ab_glyph::FontRef;
+        use image::{Rgb, RgbImage};
+
+        let font_data = std::fs::read("./test/DejaVuSansMono.ttf").unwrap();
+        let font = FontRef::try_from_slice(&font_data).unwrap();
+        let (background, color) = (Rgb([20, 40, 60]), Rgb([255, 255, 255]));
+        let gray = RgbImage::from_pixel(200, 30, Rgb([128, 128, 128]));
+
+        let img = super::add_title_strip(&gray, "Title", 24, &font, background, color).unwrap();
+        assert_eq!(img.dimensions(), (200, 30 + 24));
+        let below = img.enumerate_pixels().filter(|(_, y, _)| *y >= 24);
+        assert!(below
+            .map(|(_, _, pixel)| pixel)
+            .all(|pixel| *pixel == Rgb([128, 128, 128])));
+
+        // text is drawn near the middle of the strip, which is background everywhere else
+        let text: Vec<_> = img
+            .enumerate_pixels()
+            .filter(|(_, y, pixel)| *y < 24 && **pixel != background)
+            .map(|(x, _, _)| x)
+            .collect();
+        assert!(!text.is_empty());
+        let (left, right) = (*text.iter().min().unwrap(), *text.iter().max().unwrap());
+        assert!(left > 50 && right < 150, "text spans {left}..={right}");
+        assert!(
+            (left + right).abs_diff(200) < 10,
+            "text spans {left}..={right}"
+        );
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_blended_opacity() {
+        use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+        let blue = RgbImage::from_pixel(4, 4, Rgb([0, 0, 255]));
+        let red = RgbImage::from_pixel(2, 2, Rgb([255, 0, 0]));
+        let blits = [
+            super::ImageBlit::new(&blue, 0, 0),
+            super::ImageBlit::new(&red, 1, 1).with_opacity(0.5),
+        ];
+        let img = super::place_images_in_buffer_blended(&blits).unwrap();
+        let close = |a: u8, b: u8| a.abs_diff(b) <= 1;
+        let [r, g, b] = img.get_pixel(1, 1).0;
+        assert!(
+            close(r, 128) && g == 0 && close(b, 128),
+            "blended to {:?}",
+            [r, g, b]
+        );
+        // outside the red image the blue is untouched
+        assert_eq!(img.get_pixel(0, 0), &Rgb([0, 0, 255]));
+
+        // with alpha the opacity stacks with the image's own
+        let base = RgbaImage::from_pixel(2, 2, Rgba([0, 0, 255, 255]));
+        let half_red = RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 128]));
+        let blits = [
+            super::ImageBlit::new(&base, 0, 0),
+            super::ImageBlit::new(&half_red, 0, 0).with_opacity(0.5),
+        ];
+        let img = super::place_images_in_buffer_blended(&blits).unwrap();
+        let [r, _, b, a] = img.get_pixel(0, 0).0;
+        assert!(
+            close(r, 64) && close(b, 191) && close(a, 255),
+            "blended to {:?}",
+            [r, b, a]
+        );
+    }
+
+    #[test]
+    fn test_concat_and_encode_under() {
+        let imgs: Vec<_> = (1..=4)
+            .map(|i| image::open(format!("./test/{i}.png")).unwrap().into_rgb8())
+            .collect();
+
+        let vertical = super::ConcatDirection::Vertical;
+        let jpeg = super::concat_and_encode_under(&imgs, vertical, 15_000).unwrap();
+        assert!(jpeg.len() <= 15_000, "{} bytes", jpeg.len());
+        let decoded = image::load_from_memory_with_format(&jpeg, image::ImageFormat::Jpeg).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (422, 4 * 32));
+
+        // a much larger budget leaves room for a higher quality
+        let larger = super::concat_and_encode_under(&imgs, vertical, 60_000).unwrap();
+        assert!(larger.len() > jpeg.len());
+
+        assert!(matches!(
+            super::concat_and_encode_under(&imgs, vertical, 100),
+            Err(super::ConcatError::ExceedsMaxBytes { max_bytes: 100, .. })
+        ));
+    }
+
+    #[test]
+    fn test_place_images_in_buffer_blended_premultiplied() {
+        use image::{Rgba, RgbaImage};
+
+        // an opaque red square with a semi-transparent anti-aliased edge
+        let shape = RgbaImage::from_fn(6, 6, |x, y| {
+            let edge = x == 0 || y == 0 || x == 5 || y == 5;
+            Rgba([255, 0, 0, if edge { 128 } else { 255 }])
+        });
+        // faded onto the transparent buffer, then the result flattened over white
+        let blits = [super::ImageBlit::new(&shape, 2, 2).with_opacity(0.6)];
+        let flatten = |mode| {
+            let placed = super::place_images_in_buffer_blended_with_alpha(&blits, mode).unwrap();
+            let mut out = RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255]));
+            image::imageops::overlay(&mut out, &placed, 0, 0);
+            out
+        };
+
+        let straight = flatten(super::AlphaMode::Straight);
+        let premultiplied = flatten(super::AlphaMode::Premultiplied);
+        // red over white keeps a full red channel, straight alpha darkens it at the edge
+        assert_eq!(premultiplied.get_pixel(2, 4)[0], 255);
+        assert!(
+            straight.get_pixel(2, 4)[0] < 240,
+            "{:?}",
+            straight.get_pixel(2, 4)
+        );
+        // and both agree, give or take rounding, on how much white shows through
+        let (s, p) = (
+            straight.get_pixel(2, 4)[1],
+            premultiplied.get_pixel(2, 4)[1],
+        );
+        assert!(s.abs_diff(p) <= 2, "{s} vs {p}");
+    }
+
+    #[test]
+    fn test_grid_concat_images_with_fill() {
+        use image::{Rgb, RgbImage};
+
+        let imgs: Vec<_> = (0..4u8)
+            .map(|i| RgbImage::from_pixel(5, 5, Rgb([i * 60, 0, 0])))
+            .collect();
+        // index of the image drawn in each cell, reading cells left to right, top to bottom
+        let cells = |img: &RgbImage| -> Vec<u8> {
+            [(0, 0), (5, 0), (0, 5), (5, 5)]
+                .iter()
+                .map(|&(x, y)| img.get_pixel(x, y)[0] / 60)
+                .collect()
+        };
+
+        let rows =
+            super::grid_concat_images_with_fill(&imgs, 2, super::FillOrder::RowMajor).unwrap();
+        assert_eq!(cells(&rows), vec![0, 1, 2, 3]);
+        assert_eq!(rows, super::grid_concat_images(&imgs, 2).unwrap());
+
+        let cols =
+            super::grid_concat_images_with_fill(&imgs, 2, super::FillOrder::ColumnMajor).unwrap();
+        assert_eq!(cells(&cols), vec![0, 2, 1, 3]);
+
+        // 3 images in 2 columns leave the bottom of the second column empty
+        let blits =
+            super::get_grid_blits_with_fill(&imgs[..3], 2, super::FillOrder::ColumnMajor, 0, 0);
+        let origins: Vec<_> = blits.iter().map(|blit| (blit.x, blit.y)).collect();
+        assert_eq!(origins, vec![(0, 0), (0, 5), (5, 0)]);
+    }
+
+    #[test]
+    fn test_single_image_passthrough() {
+        let img = image::open("./test/1.png").unwrap().into_rgba8();
+        let imgs = [img.clone()];
+        for direction in [
+            super::ConcatDirection::Vertical,
+            super::ConcatDirection::Horizontal,
+        ] {
+            let concat = super::concat_images(&imgs, direction).unwrap();
+            assert_images_equal(&concat, &img);
+        }
+
+        let placed = super::place_images_in_buffer(&[super::ImageBlit::new(&img, 0, 0)]).unwrap();
+        assert_images_equal(&placed, &img);
+    }
+
+    #[test]
+    fn test_load_and_vert_concat_images_auto() {
+        use image::{DynamicImage, GrayImage, Luma};
+        let gray1 = GrayImage::from_fn(3, 2, |x, y| Luma([(x * 40 + y * 7) as u8]));
+        let gray2 = GrayImage::from_pixel(2, 3, Luma([200]));
+        let paths = [temp_path("auto_gray1.png"), temp_path("auto_gray2.png")];
+        gray1.save(&paths[0]).unwrap();
+        gray2.save(&paths[1]).unwrap();
+
+        let img = super::load_and_vert_concat_images_auto(&paths).unwrap();
+        let DynamicImage::ImageLuma8(img) = img else {
+            panic!("expected a GrayImage, got {:?}", img.color());
+        };
+        let expected =
+            super::concat_images(&[gray1, gray2], super::ConcatDirection::Vertical).unwrap();
+        assert_images_equal(&img, &expected);
+
+        // one RGBA image promotes the rest
+        let rgba = temp_path("auto_rgba.png");
+        image::RgbaImage::from_pixel(1, 1, image::Rgba([1, 2, 3, 4]))
+            .save(&rgba)
+            .unwrap();
+        let img =
+            super::load_and_vert_concat_images_auto(&[paths[0].clone(), rgba.clone()]).unwrap();
+        assert_eq!(img.color(), image::ColorType::Rgba8);
+        assert_eq!(img.to_rgba8().get_pixel(1, 0).0, [40, 40, 40, 255]);
+
+        for path in paths.iter().chain([&rgba]) {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_load_with_retry() {
+        use std::cell::Cell;
+        use std::io::{Error, ErrorKind};
+        use std::time::Duration;
+
+        let paths = [PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")];
+        let expected = super::load_and_vert_concat_images(&paths).unwrap();
+
+        // every file fails to open twice before it can be read
+        let attempts = Cell::new(0);
+        let flaky_open = |path: &std::path::Path| {
+            attempts.set(attempts.get() + 1);
+            match attempts.get() % 3 {
+                1 => Err(Error::from(ErrorKind::Interrupted)),
+                2 => Err(Error::from(ErrorKind::TimedOut)),
+                _ => std::fs::File::open(path),
+            }
+        };
+        let img = super::load_with_retry_from(&paths, 2, Duration::ZERO, flaky_open).unwrap();
+        assert_images_equal(&img, &expected);
+        assert_eq!(attempts.get(), 6);
+
+        // one retry isn't enough
+        attempts.set(0);
+        let err = super::load_with_retry_from(&paths, 1, Duration::ZERO, flaky_open).unwrap_err();
+        assert!(
+            matches!(err, super::ConcatError::Io(ref err) if err.kind() == ErrorKind::TimedOut)
+        );
+
+        // errors that won't clear up aren't retried
+        attempts.set(0);
+        let missing = |_: &std::path::Path| {
+            attempts.set(attempts.get() + 1);
+            std::fs::File::open("./test/missing.png")
+        };
+        assert!(super::load_with_retry_from(&paths, 5, Duration::ZERO, missing).is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_error_non_utf8_path() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let missing = PathBuf::from(OsStr::from_bytes(b"./test/missing-\xff.png"));
+        let img_result = super::load_and_vert_concat_images(std::slice::from_ref(&missing));
+        assert!(matches!(img_result, Err(super::ConcatError::Io(_))));
+        let img_result =
+            super::load_and_concat_dir(&missing, "*", super::ConcatDirection::Vertical);
+        assert!(img_result.is_err());
     }
 }
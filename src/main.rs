@@ -1,19 +1,162 @@
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 
-use image::RgbImage;
+use clap::{Parser, ValueEnum};
+use image::codecs::jpeg::JpegEncoder;
+use image::{ImageError, ImageFormat, RgbImage};
 use image_concat_rs::{
-    column_concat_images, concat_images, load_and_column_concat_images,
-    load_and_vert_concat_images, ConcatDirection,
+    column_concat_images, concat_images, concat_images_with_options, load_and_column_concat_images,
+    load_and_vert_concat_images, ConcatDirection, ConcatOptions, Spacing,
 };
 
+/// Concatenate images vertically, horizontally or into columns
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    /// Images to concatenate, in order
+    #[arg(required_unless_present = "demo")]
+    images: Vec<PathBuf>,
+
+    /// Path to save the concatenated image to
+    #[arg(short, long, required_unless_present = "demo")]
+    output: Option<PathBuf>,
+
+    /// Direction to concatenate images in
+    #[arg(short, long, value_enum, default_value_t = Direction::Vertical)]
+    direction: Direction,
+
+    /// Split images into this many vertical columns
+    #[arg(short, long, conflicts_with_all = ["direction", "spacing"])]
+    columns: Option<usize>,
+
+    /// Gap in pixels between adjacent images
+    #[arg(short, long, default_value_t = 0)]
+    spacing: u32,
+
+    /// Format to save the output as, inferred from the output extension if not given
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// JPEG quality from 1 to 100
+    #[arg(short, long, default_value_t = 75, value_parser = clap::value_parser!(u8).range(1..=100))]
+    quality: u8,
+
+    /// Run the original demo, concatenating ./test/1.png..8.png into several layouts
+    #[arg(long, conflicts_with_all = ["images", "output"])]
+    demo: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Direction {
+    Vertical,
+    Horizontal,
+}
+
+impl From<Direction> for ConcatDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Vertical => ConcatDirection::Vertical,
+            Direction::Horizontal => ConcatDirection::Horizontal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Png,
+    Jpeg,
+    Webp,
+    Bmp,
+}
+
+impl Format {
+    /// Supported format matching the extension of `path`
+    fn from_path(path: &Path) -> Result<Self, String> {
+        match ImageFormat::from_path(path) {
+            Ok(ImageFormat::Png) => Ok(Format::Png),
+            Ok(ImageFormat::Jpeg) => Ok(Format::Jpeg),
+            Ok(ImageFormat::WebP) => Ok(Format::Webp),
+            Ok(ImageFormat::Bmp) => Ok(Format::Bmp),
+            _ => Err(format!(
+                "Can't infer a supported output format from {}, use --format",
+                path.display()
+            )),
+        }
+    }
+}
+
+impl From<Format> for ImageFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Png => ImageFormat::Png,
+            Format::Jpeg => ImageFormat::Jpeg,
+            Format::Webp => ImageFormat::WebP,
+            Format::Bmp => ImageFormat::Bmp,
+        }
+    }
+}
+
 fn save_img(img: RgbImage, save_path: &str) {
-    match img.save_with_format(save_path, image::ImageFormat::Png) {
+    match img.save_with_format(save_path, ImageFormat::Png) {
         Ok(_) => println!("Saved image to {save_path}"),
         Err(err) => println!("Error saving to {save_path}: {err}"),
     }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    if args.demo {
+        return demo();
+    }
+
+    // Convert on load so any mix of source pixel types can be concatenated
+    let imgs = args
+        .images
+        .iter()
+        .map(|path| {
+            image::open(path)
+                .map(|img| img.into_rgb8())
+                .map_err(|err| format!("Error opening image {}: {err}", path.display()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let img = match args.columns {
+        Some(columns) => column_concat_images(&imgs, columns)?,
+        None => {
+            let options = ConcatOptions {
+                spacing: Spacing::Fixed(args.spacing),
+                ..ConcatOptions::new(args.direction.into())
+            };
+            concat_images_with_options(&imgs, &options)?
+        }
+    };
+
+    // Presence is enforced by clap when not running the demo
+    let output = args.output.expect("output is required");
+    let format = match args.format {
+        Some(format) => format,
+        None => Format::from_path(&output)?,
+    };
+    save(&img, &output, format, args.quality)?;
+    println!("Saved image to {}", output.display());
+
+    Ok(())
+}
+
+fn save(img: &RgbImage, path: &Path, format: Format, quality: u8) -> Result<(), ImageError> {
+    match format {
+        // JPEG is the only format with a quality setting, everything else can use the defaults
+        Format::Jpeg => {
+            let file = BufWriter::new(File::create(path)?);
+            img.write_with_encoder(JpegEncoder::new_with_quality(file, quality))
+        }
+        format => img.save_with_format(path, format.into()),
+    }
+}
+
+fn demo() -> Result<(), Box<dyn std::error::Error>> {
     // Make a Vec of image PathBufs
     let img_count = 8;
     let img_paths: Vec<_> = (1..=img_count)
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use image::error::{DecodingError, ImageFormatHint};
+use image::{ImageFormat, ImageReader, RgbImage};
+
+use crate::stream::png_error;
+use crate::ConcatError;
+
+/// Saves an image as a PNG, carrying over the DPI of the source images.
+///
+/// The physical pixel dimensions (`pHYs` chunk) of the first PNG source that has
+/// them are written to the output. Sources without them, including non-PNG
+/// sources, are skipped. If sources disagree, the first is used unless `strict`
+/// is set, in which case `ConcatError::ConflictingDpi` is returned.
+///
+/// # Arguments
+/// * `img` - Concatenated image to save
+/// * `source_paths` - Slice of PathBufs to the images `img` was made from
+/// * `out_path` - Path the PNG will be written to
+/// * `strict` - Error instead of using the first DPI when sources disagree
+///
+/// # Returns
+/// * `Result<(), ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::{load_and_vert_concat_images, save_with_metadata};
+/// use std::path::PathBuf;
+/// let paths = [PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")];
+/// let img = load_and_vert_concat_images(&paths).unwrap();
+/// let out_path = std::env::temp_dir().join("save_with_metadata_doc.png");
+/// let result = save_with_metadata(&img, &paths, &out_path, false);
+/// ```
+pub fn save_with_metadata(
+    img: &RgbImage,
+    source_paths: &[PathBuf],
+    out_path: &Path,
+    strict: bool,
+) -> Result<(), ConcatError> {
+    let mut pixel_dims = None;
+    for path in source_paths {
+        let Some(dims) = read_pixel_dims(path)? else {
+            continue;
+        };
+
+        match pixel_dims {
+            None => pixel_dims = Some(dims),
+            Some(first) if strict && !same_dims(first, dims) => {
+                return Err(ConcatError::ConflictingDpi)
+            }
+            Some(_) => {}
+        }
+    }
+
+    let file = std::io::BufWriter::new(File::create(out_path)?);
+    let mut encoder = png::Encoder::new(file, img.width(), img.height());
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_pixel_dims(pixel_dims);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(img.as_raw()).map_err(png_error)?;
+    writer.finish().map_err(png_error)?;
+
+    Ok(())
+}
+
+/// `PixelDimensions` doesn't implement PartialEq
+fn same_dims(a: png::PixelDimensions, b: png::PixelDimensions) -> bool {
+    (a.xppu, a.yppu, a.unit) == (b.xppu, b.yppu, b.unit)
+}
+
+/// Reads the `pHYs` chunk of a PNG, returning None for other formats
+fn read_pixel_dims(path: &Path) -> Result<Option<png::PixelDimensions>, ConcatError> {
+    if ImageReader::open(path)?.with_guessed_format()?.format() != Some(ImageFormat::Png) {
+        return Ok(None);
+    }
+
+    let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+    let reader = decoder.read_info().map_err(|err| match err {
+        png::DecodingError::IoError(err) => ConcatError::Io(err),
+        err => ConcatError::Decode(image::ImageError::Decoding(DecodingError::new(
+            ImageFormatHint::Exact(ImageFormat::Png),
+            err,
+        ))),
+    })?;
+
+    Ok(reader.info().pixel_dims)
+}
@@ -0,0 +1,247 @@
+use image::{ImageBuffer, Pixel};
+
+use crate::{copy_image_into, ConcatError, Rect};
+
+/// Where one image was packed by `pack_bins`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlacedRect {
+    /// Index of the image in the slice that was packed
+    pub index: usize,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// One bin filled by `pack_bins` and the images packed into it
+pub type PackedBin<P> = (ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>, Vec<PlacedRect>);
+
+/// How a free rect is scored for an image, lower is better
+#[derive(Clone, Copy)]
+pub(crate) enum Heuristic {
+    /// Leaves the smallest gap along the free rect's shorter leftover side,
+    /// the best general choice for filling fixed size bins
+    BestShortSideFit,
+    /// Places images as high up, then as far left, as possible, which keeps
+    /// the used height of a very tall bin down
+    BottomLeft,
+}
+
+/// A bin being filled with the MaxRects algorithm
+///
+/// Every maximal free rectangle is tracked, and they may overlap. Placing an
+/// image splits each free rect it touches into the up to four rects around it,
+/// then drops any free rect that's contained in another.
+pub(crate) struct MaxRects {
+    free: Vec<Rect>,
+    heuristic: Heuristic,
+}
+
+impl MaxRects {
+    /// Empty bin of the given size
+    pub(crate) fn new(width: u32, height: u32, heuristic: Heuristic) -> Self {
+        MaxRects {
+            free: vec![Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            }],
+            heuristic,
+        }
+    }
+
+    /// Reserves the best spot for a `width` x `height` image, or returns None if it doesn't fit
+    pub(crate) fn insert(&mut self, width: u32, height: u32) -> Option<Rect> {
+        let free = self
+            .free
+            .iter()
+            .filter(|free| free.width >= width && free.height >= height)
+            .min_by_key(|free| self.score(free, width, height))?;
+        let placed = Rect {
+            x: free.x,
+            y: free.y,
+            width,
+            height,
+        };
+        if width == 0 || height == 0 {
+            return Some(placed);
+        }
+
+        let mut split = Vec::with_capacity(self.free.len() + 4);
+        for free in self.free.drain(..) {
+            if overlaps(&free, &placed) {
+                split.extend(split_around(&free, &placed));
+            } else {
+                split.push(free);
+            }
+        }
+        self.free = prune(split);
+        Some(placed)
+    }
+
+    fn score(&self, free: &Rect, width: u32, height: u32) -> (u64, u64) {
+        match self.heuristic {
+            Heuristic::BestShortSideFit => {
+                let leftover_x = u64::from(free.width - width);
+                let leftover_y = u64::from(free.height - height);
+                (leftover_x.min(leftover_y), leftover_x.max(leftover_y))
+            }
+            Heuristic::BottomLeft => (u64::from(free.y) + u64::from(height), u64::from(free.x)),
+        }
+    }
+}
+
+// Every rect lies within its bin, so its right and bottom edges can't overflow a u32
+
+/// Whether two rects share any area
+fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+/// Whether `inner` lies entirely within `outer`
+fn contains(outer: &Rect, inner: &Rect) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+/// Parts of `free` left above, below, left and right of `placed`, which must overlap it
+fn split_around(free: &Rect, placed: &Rect) -> impl Iterator<Item = Rect> {
+    let free_right = free.x + free.width;
+    let free_bottom = free.y + free.height;
+    let placed_right = placed.x + placed.width;
+    let placed_bottom = placed.y + placed.height;
+
+    let above = (placed.y > free.y).then(|| Rect {
+        height: placed.y - free.y,
+        ..*free
+    });
+    let below = (placed_bottom < free_bottom).then(|| Rect {
+        y: placed_bottom,
+        height: free_bottom - placed_bottom,
+        ..*free
+    });
+    let left = (placed.x > free.x).then(|| Rect {
+        width: placed.x - free.x,
+        ..*free
+    });
+    let right = (placed_right < free_right).then(|| Rect {
+        x: placed_right,
+        width: free_right - placed_right,
+        ..*free
+    });
+    [above, below, left, right].into_iter().flatten()
+}
+
+/// Removes free rects that are entirely inside another, keeping one of any duplicates
+fn prune(free: Vec<Rect>) -> Vec<Rect> {
+    free.iter()
+        .enumerate()
+        .filter(|&(i, rect)| {
+            !free.iter().enumerate().any(|(j, other)| {
+                // An identical rect only survives at its first position
+                i != j && contains(other, rect) && (other != rect || j < i)
+            })
+        })
+        .map(|(_, rect)| *rect)
+        .collect()
+}
+
+/// Order images are packed in, largest side first, which packs much tighter than input order
+pub(crate) fn packing_order(sizes: &[(u32, u32)]) -> Vec<usize> {
+    let mut order: Vec<_> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| {
+        let (width, height) = sizes[i];
+        std::cmp::Reverse((width.max(height), width.min(height)))
+    });
+    order
+}
+
+/// Packs images into as few fixed size bins as possible with the MaxRects algorithm
+///
+/// Images are packed largest first into the first bin with room for them, and a
+/// new bin is started whenever none has, so heterogeneous sizes waste far less
+/// space than packing them in rows. Images are never rotated. Each bin is a
+/// full `bin_size` image, e.g. for power of two texture atlases, with anything
+/// not covered by an image left zero filled.
+///
+/// # Arguments
+/// * `images` - Slice of ImageBuffers to pack
+/// * `bin_size` - Width and height of each bin
+///
+/// # Returns
+/// * `Result<Vec<PackedBin>, ConcatError>` - Each bin and the images placed in it
+/// * `ConcatError::TooLargeForBin` if an image is bigger than `bin_size`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::pack_bins;
+/// let imgs = vec![image::RgbaImage::new(48, 48); 3];
+/// let bins = pack_bins(&imgs, (64, 64)).unwrap();
+/// assert_eq!(bins.len(), 3);
+/// ```
+pub fn pack_bins<P: Pixel>(
+    images: &[ImageBuffer<P, Vec<P::Subpixel>>],
+    bin_size: (u32, u32),
+) -> Result<Vec<PackedBin<P>>, ConcatError> {
+    if images.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    let (bin_width, bin_height) = bin_size;
+    let sizes: Vec<_> = images.iter().map(|img| img.dimensions()).collect();
+    let mut bins: Vec<(MaxRects, Vec<PlacedRect>)> = Vec::new();
+    for index in packing_order(&sizes) {
+        let (width, height) = sizes[index];
+        if width > bin_width || height > bin_height {
+            return Err(ConcatError::TooLargeForBin {
+                index,
+                width,
+                height,
+            });
+        }
+
+        let found = bins
+            .iter_mut()
+            .enumerate()
+            .find_map(|(i, (bin, _))| Some((i, bin.insert(width, height)?)));
+        let (bin_index, rect) = match found {
+            Some(found) => found,
+            None => {
+                let mut bin = MaxRects::new(bin_width, bin_height, Heuristic::BestShortSideFit);
+                let rect = bin.insert(width, height).expect("image fits an empty bin");
+                bins.push((bin, Vec::new()));
+                (bins.len() - 1, rect)
+            }
+        };
+        bins[bin_index].1.push(PlacedRect {
+            index,
+            x: rect.x,
+            y: rect.y,
+            width,
+            height,
+        });
+    }
+
+    Ok(bins
+        .into_iter()
+        .map(|(_, mut placed)| {
+            placed.sort_by_key(|rect| rect.index);
+            let mut buffer = ImageBuffer::new(bin_width, bin_height);
+            for rect in &placed {
+                let img = &images[rect.index];
+                let src = Rect {
+                    x: 0,
+                    y: 0,
+                    width: rect.width,
+                    height: rect.height,
+                };
+                copy_image_into(&mut buffer, img, src, rect.x, rect.y);
+            }
+            (buffer, placed)
+        })
+        .collect())
+}
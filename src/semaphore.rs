@@ -0,0 +1,48 @@
+use std::sync::{Condvar, Mutex, PoisonError};
+
+/// Counting semaphore bounding how many tasks hold a permit at once
+pub(crate) struct Semaphore {
+    permits: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Held while a task runs, returning its permit to the semaphore when dropped
+pub(crate) struct Permit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Semaphore {
+    /// Semaphore handing out at most `permits` permits at a time
+    pub(crate) fn new(permits: usize) -> Self {
+        Semaphore {
+            permits: Mutex::new(permits),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a permit is free and takes it
+    pub(crate) fn acquire(&self) -> Permit<'_> {
+        // The count is only ever changed in one step, so it's still valid after a panic
+        let mut permits = self.permits.lock().unwrap_or_else(PoisonError::into_inner);
+        while *permits == 0 {
+            permits = self
+                .released
+                .wait(permits)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *permits -= 1;
+        Permit { semaphore: self }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut permits = self
+            .semaphore
+            .permits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *permits += 1;
+        self.semaphore.released.notify_one();
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use image::error::{EncodingError, ImageFormatHint};
+use image::{ImageDecoder, ImageFormat, Rgb};
+
+use crate::{open_decoder, ConcatError};
+
+/// Loads given images and vertically concatenates them straight into a PNG file.
+///
+/// Rows are encoded as each image is decoded so the full output is never held
+/// in memory, only one decoded source image at a time. This makes it possible to
+/// concatenate images whose combined size wouldn't fit in RAM, at the cost of
+/// only supporting vertical concatenation of 8-bit RGB images into a PNG.
+///
+/// Narrower images are padded with black on the right, matching
+/// `load_and_vert_concat_images`.
+///
+/// Each file is opened twice, once up front to read its header and check its
+/// color type, then again when its rows are written. Only one file is held open
+/// at a time, so long path lists don't run into the open file limit.
+///
+/// # Arguments
+/// * `image_paths` - Slice of PathBufs to images to load
+/// * `out_path` - Path the PNG will be written to
+///
+/// # Returns
+/// * `Result<(), ConcatError>`
+///
+/// # Example
+/// ```
+/// use image_concat_rs::stream_vert_concat_to_png;
+/// use std::path::PathBuf;
+/// let out_path = std::env::temp_dir().join("stream_vert_concat_to_png_doc.png");
+/// let result = stream_vert_concat_to_png(&[PathBuf::from("./test/1.png"), PathBuf::from("./test/2.png")], &out_path);
+/// ```
+pub fn stream_vert_concat_to_png(
+    image_paths: &[PathBuf],
+    out_path: &Path,
+) -> Result<(), ConcatError> {
+    if image_paths.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    // Read every header first so the PNG header can be written with the final dimensions,
+    // closing each file again straight away
+    let mut total_height: u32 = 0;
+    let mut max_width = 0;
+    for path in image_paths {
+        let (width, height) = open_decoder::<Rgb<u8>>(path)?.dimensions();
+        total_height = total_height
+            .checked_add(height)
+            .ok_or(ConcatError::DimensionOverflow)?;
+        max_width = max_width.max(width);
+    }
+
+    let file = BufWriter::new(File::create(out_path)?);
+    let mut encoder = png::Encoder::new(file, max_width, total_height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    let mut stream = writer.stream_writer().map_err(png_error)?;
+
+    // Row of padding appended to images narrower than the output
+    let out_row_len = max_width as usize * 3;
+    let padding = vec![0; out_row_len];
+
+    for path in image_paths {
+        let decoder = open_decoder::<Rgb<u8>>(path)?;
+        let height = decoder.dimensions().1 as usize;
+        let mut decoded = vec![0; decoder.total_bytes() as usize];
+        decoder.read_image(&mut decoded)?;
+
+        if height == 0 {
+            continue;
+        }
+        let row_len = decoded.len() / height;
+        for row in decoded.chunks_exact(row_len) {
+            stream.write_all(row)?;
+            stream.write_all(&padding[row_len..])?;
+        }
+    }
+
+    stream.finish().map_err(png_error)?;
+    writer.finish().map_err(png_error)?;
+
+    Ok(())
+}
+
+/// Converts a png encoding error, keeping IO failures as ConcatError::Io
+pub(crate) fn png_error(err: png::EncodingError) -> ConcatError {
+    match err {
+        png::EncodingError::IoError(err) => ConcatError::Io(err),
+        err => ConcatError::Decode(image::ImageError::Encoding(EncodingError::new(
+            ImageFormatHint::Exact(ImageFormat::Png),
+            err,
+        ))),
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Unique path in the system temp dir so parallel tests don't clobber each other
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "image-concat-rs-cli-{}-{}",
+        std::process::id(),
+        name
+    ))
+}
+
+fn run(args: &[&std::ffi::OsStr]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_image-concat-rs"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_cli_concat() {
+    let first = temp_path("first.png");
+    let second = temp_path("second.png");
+    image::RgbImage::new(20, 10).save(&first).unwrap();
+    image::RgbImage::new(30, 15).save(&second).unwrap();
+
+    let vertical = temp_path("vertical.png");
+    let output = run(&[
+        "--direction".as_ref(),
+        "vertical".as_ref(),
+        "--spacing".as_ref(),
+        "5".as_ref(),
+        "--output".as_ref(),
+        vertical.as_os_str(),
+        first.as_os_str(),
+        second.as_os_str(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(image::image_dimensions(&vertical).unwrap(), (30, 30));
+
+    let columns = temp_path("columns.png");
+    let output = run(&[
+        "--columns".as_ref(),
+        "2".as_ref(),
+        "-o".as_ref(),
+        columns.as_os_str(),
+        first.as_os_str(),
+        second.as_os_str(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(image::image_dimensions(&columns).unwrap(), (50, 15));
+
+    for path in [first, second, vertical, columns] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_cli_requires_output() {
+    let output = run(&["./test/1.png".as_ref()]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_output_format() {
+    let first = temp_path("format_first.png");
+    let second = temp_path("format_second.png");
+    image::RgbImage::new(20, 10).save(&first).unwrap();
+    image::RgbImage::new(20, 15).save(&second).unwrap();
+
+    // format inferred from each extension
+    for (name, format) in [
+        ("out.png", image::ImageFormat::Png),
+        ("out.jpg", image::ImageFormat::Jpeg),
+        ("out.webp", image::ImageFormat::WebP),
+        ("out.bmp", image::ImageFormat::Bmp),
+    ] {
+        let out = temp_path(name);
+        let output = run(&[
+            "-o".as_ref(),
+            out.as_os_str(),
+            first.as_os_str(),
+            second.as_os_str(),
+        ]);
+        assert!(output.status.success(), "{output:?}");
+
+        let img = image::ImageReader::open(&out)
+            .unwrap()
+            .with_guessed_format()
+            .unwrap();
+        assert_eq!(img.format(), Some(format));
+        assert_eq!(img.into_dimensions().unwrap(), (20, 25));
+        std::fs::remove_file(out).unwrap();
+    }
+
+    // explicit format and quality override the extension
+    let out = temp_path("out.img");
+    let output = run(&[
+        "--format".as_ref(),
+        "jpeg".as_ref(),
+        "--quality".as_ref(),
+        "50".as_ref(),
+        "-o".as_ref(),
+        out.as_os_str(),
+        first.as_os_str(),
+        second.as_os_str(),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+    let decoded = image::ImageReader::open(&out)
+        .unwrap()
+        .with_guessed_format()
+        .unwrap()
+        .decode()
+        .unwrap();
+    assert_eq!((decoded.width(), decoded.height()), (20, 25));
+
+    // unknown extension without --format
+    let output = run(&["-o".as_ref(), out.as_os_str(), first.as_os_str()]);
+    assert!(!output.status.success());
+
+    for path in [first, second, out] {
+        std::fs::remove_file(path).unwrap();
+    }
+}